@@ -1,5 +1,5 @@
 use crate::combinators::{map, pure};
-use crate::core::{return_none, Parsable, Parser};
+use crate::core::{return_none, Parsable, Parser, Stream};
 
 /// # Combinator: `empty`
 ///
@@ -30,6 +30,10 @@ pub fn empty<'f, A: 'f, S: 'f>() -> Parser<'f, A, S> {
 /// if the first parser succeeds then its result is returned,
 /// otherwise the result of the second parser is returned.
 ///
+/// The second parser is only tried if the first one fails without
+/// consuming any input. Wrap the first parser with `attempt` to
+/// backtrack anyway, or use `cut` to make failures fatal.
+///
 /// # Properties
 ///
 /// Should satisfy [Alternative laws](https://wiki.haskell.org/Typeclassopedia#Laws_6).
@@ -66,21 +70,100 @@ pub fn empty<'f, A: 'f, S: 'f>() -> Parser<'f, A, S> {
 /// assert_eq!("hhh", st.as_str());
 /// assert_eq!(0, logs.len());
 /// ```
-pub fn or<'f, A: 'f, S: Clone>(
+pub fn or<'f, A: 'f, S: Stream>(
     p1: impl Parsable<Stream = S, Result = A> + 'f,
     p2: impl Parsable<Stream = S, Result = A> + 'f,
 ) -> Parser<'f, A, S> {
     Parser::new(move |stream: &mut S, logger| {
         let (st, lg) = (stream.clone(), logger.clone());
-        p1.parse(stream, logger).or_else(|| {
+        let outer = logger.enter();
+        let res = p1.parse(stream, logger).or_else(|| {
+            // Input consumed by `p1` commits to it, even if `p1` did not
+            // mark it in the logger
+            if logger.is_committed() || logger.has_consumed(st.index(), stream.index()) {
+                return return_none(stream, &st, logger);
+            }
             *stream = st.clone();
-            *logger = lg;
+            logger.restore(lg);
+            logger.enter();
             p2.parse(stream, logger)
-                .or_else(|| return_none(stream, &st))
+                .or_else(|| return_none(stream, &st, logger))
+        });
+        logger.leave(outer, res.is_some());
+        res
+    })
+}
+
+/// # Combinator: `attempt` (function ver.)
+///
+/// Apply given parser, and pretend that no input has been consumed
+/// if it fails, so that `or` could still try other alternatives.
+/// Failures after a `cut` are not affected.
+///
+/// # Example
+/// ```
+/// use parsic::combinators::*;
+/// use parsic::core::Parsable;
+/// use parsic::primitives::{char, CharStream};
+///
+/// // Without `attempt`, 'A' is consumed before 'B' fails,
+/// // and the second alternative would not be tried.
+/// let parser = attempt(char('A').and(char('B'))).or(char('A').and(char('C')));
+///
+/// let mut st = CharStream::new("AC");
+/// let (res, logs) = parser.exec(&mut st);
+///
+/// assert_eq!(Some(('A', 'C')), res);
+/// assert_eq!("", st.as_str());
+/// assert_eq!(0, logs.len());
+/// ```
+pub fn attempt<'f, A: 'f, S: Stream>(
+    p: impl Parsable<Stream = S, Result = A> + 'f,
+) -> Parser<'f, A, S> {
+    Parser::new(move |stream: &mut S, logger| {
+        let (st, consumed) = (stream.clone(), logger.consumed);
+        p.parse(stream, logger).or_else(|| {
+            if !logger.cut {
+                *stream = st.clone();
+                logger.consumed = consumed;
+            }
+            None
         })
     })
 }
 
+/// # Combinator: `cut`
+///
+/// A parser that consumes no item and always succeeds. Once passed,
+/// any failure after it within the current alternative is fatal:
+/// neither `or`, `optional`, `many` nor `attempt` would recover from it.
+///
+/// # Example
+/// ```
+/// use parsic::combinators::*;
+/// use parsic::core::Parsable;
+/// use parsic::primitives::{char, literal, CharStream};
+///
+/// // After keyword "if", a '(' is mandatory,
+/// // even though `attempt` is used
+/// let parser = literal("if").right(cut()).right(char('('))
+///                 .attempt()
+///                 .or(literal("if").right(char(' ')));
+///
+/// let mut st = CharStream::new("if x");
+/// let (res, logs) = parser.exec(&mut st);
+///
+/// assert_eq!(None, res);
+/// assert_eq!("if x", st.as_str());
+/// assert_eq!(1, logs.len());
+/// ```
+pub fn cut<'f, S>() -> Parser<'f, (), S> {
+    Parser::new(|_: &mut S, logger| {
+        logger.cut = true;
+        Some(())
+    })
+}
+
 /// # Combinator: `optional` (function ver.)
 ///
 /// Apply given parser **at most one time**. Denote the result
@@ -106,7 +189,7 @@ pub fn or<'f, A: 'f, S: Clone>(
 /// assert_eq!(("ttql", "ql") ,(st1.as_str(), st2.as_str()));
 /// assert_eq!((0, 0), (logs1.len(), logs2.len()));
 /// ```
-pub fn optional<'f, A: Clone + 'f, S: Clone + 'f>(
+pub fn optional<'f, A: Clone + 'f, S: Stream + 'f>(
     p: impl Parsable<Stream = S, Result = A> + 'f,
 ) -> Parser<'f, Option<A>, S> {
     or(map(p, Some), pure(None))
@@ -119,6 +202,10 @@ pub trait AlternativeExt<'f, A: 'f, S>: Parsable<Stream = S, Result = A> {
     /// if the first parser succeeds then its result is returned,
     /// otherwise the result of the second parser is returned.
     ///
+    /// The second parser is only tried if the first one fails without
+    /// consuming any input. Wrap the first parser with `attempt` to
+    /// backtrack anyway, or use `cut` to make failures fatal.
+    ///
     /// # Properties
     ///
     /// Should satisfy [Alternative laws](https://wiki.haskell.org/Typeclassopedia#Laws_6).
@@ -157,12 +244,43 @@ pub trait AlternativeExt<'f, A: 'f, S>: Parsable<Stream = S, Result = A> {
     /// ```
     fn or(self, p: impl Parsable<Stream = S, Result = A> + 'f) -> Parser<'f, A, S>
     where
-        S: Clone,
+        S: Stream,
         Self: Sized + 'f,
     {
         or(self, p)
     }
 
+    /// # Combinator: `attempt`
+    ///
+    /// Apply given parser, and pretend that no input has been consumed
+    /// if it fails, so that `or` could still try other alternatives.
+    /// Failures after a `cut` are not affected.
+    ///
+    /// # Example
+    /// ```
+    /// use parsic::combinators::*;
+    /// use parsic::core::Parsable;
+    /// use parsic::primitives::{char, CharStream};
+    ///
+    /// // Without `attempt`, 'A' is consumed before 'B' fails,
+    /// // and the second alternative would not be tried.
+    /// let parser = char('A').and(char('B')).attempt().or(char('A').and(char('C')));
+    ///
+    /// let mut st = CharStream::new("AC");
+    /// let (res, logs) = parser.exec(&mut st);
+    ///
+    /// assert_eq!(Some(('A', 'C')), res);
+    /// assert_eq!("", st.as_str());
+    /// assert_eq!(0, logs.len());
+    /// ```
+    fn attempt(self) -> Parser<'f, A, S>
+    where
+        S: Stream,
+        Self: Sized + 'f,
+    {
+        attempt(self)
+    }
+
     /// # Combinator: `optional`
     ///
     /// Apply given parser **at most one time**. Denote the result
@@ -191,7 +309,7 @@ pub trait AlternativeExt<'f, A: 'f, S>: Parsable<Stream = S, Result = A> {
     fn optional(self) -> Parser<'f, Option<A>, S>
     where
         A: Clone,
-        S: Clone + 'f,
+        S: Stream + 'f,
         Self: Sized + 'f,
     {
         optional(self)
@@ -218,6 +336,66 @@ mod test_alternative {
         assert_eq!(1, logs.len());
     }
 
+    #[test]
+    fn no_backtrack_after_consuming() {
+        let parser = char('A').and(char('B')).or(char('A').and(char('C')));
+
+        let mut st = CharStream::new("AC");
+        let (res, logs) = parser.exec(&mut st);
+
+        assert_eq!(None, res);
+        assert_eq!("AC", st.as_str());
+        assert_eq!(1, logs.len());
+    }
+
+    #[test]
+    fn consumed_without_marking() {
+        // Advances the stream without telling the logger
+        let any = || Parser::new(|stream: &mut CharStream, _| stream.next());
+        let leaky = Parser::new(|stream: &mut CharStream, _| stream.next().filter(|_| false));
+
+        let mut st = CharStream::new("AC");
+        let parser = any().and(char('B')).or(pure(('?', '?')));
+        assert_eq!(None, parser.exec(&mut st).0);
+        assert_eq!("AC", st.as_str());
+
+        let parser = leaky.or(pure('?'));
+        assert_eq!(None, parser.exec(&mut st).0);
+        assert_eq!("AC", st.as_str());
+
+        let parser = attempt(any().and(char('B'))).or(any().and(char('C')));
+        assert_eq!(Some(('A', 'C')), parser.exec(&mut st).0);
+    }
+
+    #[test]
+    fn cut_is_scoped_to_alternative() {
+        let parser = char('A')
+            .left(cut())
+            .or(char('B'))
+            .and(char('C'))
+            .attempt()
+            .or(char('A').and(char('D')));
+
+        let mut st = CharStream::new("AD");
+        let (res, logs) = parser.exec(&mut st);
+
+        assert_eq!(Some(('A', 'D')), res);
+        assert_eq!("", st.as_str());
+        assert_eq!(0, logs.len());
+    }
+
+    #[test]
+    fn cut_is_fatal_in_many() {
+        let parser = char('A').right(cut()).right(char('B')).attempt().many();
+
+        let mut st = CharStream::new("ABABAC");
+        let (res, logs) = parser.exec(&mut st);
+
+        assert_eq!(None, res);
+        assert_eq!("ABABAC", st.as_str());
+        assert_eq!(1, logs.len());
+    }
+
     #[test]
     fn monoid_left_identity() {
         //! `empty().or(p) ~ p`
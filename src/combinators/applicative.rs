@@ -1,4 +1,4 @@
-use crate::core::{return_none, Parsable, Parser};
+use crate::core::{return_none, Parsable, Parser, Stream};
 
 /// # Combinator: `pure`
 ///
@@ -68,7 +68,7 @@ pub fn pure<'f, A: Clone + 'f, S: 'f>(x: A) -> Parser<'f, A, S> {
 /// assert_eq!("ello", st.as_str());
 /// assert_eq!(0, logs.len());
 /// ```
-pub fn compose<'f, A: 'f, B: 'f, F, S: Clone>(
+pub fn compose<'f, A: 'f, B: 'f, F, S: Stream>(
     pf: impl Parsable<Stream = S, Result = F> + 'f,
     px: impl Parsable<Stream = S, Result = A> + 'f,
 ) -> Parser<'f, B, S>
@@ -78,8 +78,8 @@ where
     Parser::new(move |stream: &mut S, logger| {
         let st = stream.clone();
         pf.parse(stream, logger)
-            .and_then(|f| px.parse(stream, logger).map(|x| f(x)))
-            .or_else(|| return_none(stream, &st))
+            .and_then(|f| px.parse(stream, logger).map(f))
+            .or_else(|| return_none(stream, &st, logger))
     })
 }
 
@@ -120,7 +120,7 @@ pub trait ApplicativeExt<'f, F: 'f, S>: Parsable<Stream = S, Result = F> {
     ) -> Parser<'f, B, S>
     where
         F: Fn(A) -> B,
-        S: Clone,
+        S: Stream,
         Self: Sized + 'f,
     {
         compose(self, px)
@@ -1,4 +1,4 @@
-use crate::core::{Msg, MsgBody, Parsable, Parser};
+use crate::core::{Msg, MsgBody, Parsable, ParseError, Parser, Stream};
 
 /// # Combinator: `info` (function ver.)
 fn info<'f, A: 'f, S>(
@@ -39,8 +39,36 @@ fn error<'f, A: 'f, S>(
     })
 }
 
+/// # Combinator: `label` (function ver.)
+fn label<'f, A: 'f, S: Stream>(
+    p: impl Parsable<Stream = S, Result = A> + 'f,
+    name: &'f str,
+) -> Parser<'f, A, S> {
+    Parser::new(move |stream: &mut S, logger| {
+        let outer = logger.error.take();
+        let pos = stream.pos();
+        let res = p.parse(stream, logger);
+        // Errors at the start position are replaced by the label
+        let err = match logger.error.take() {
+            Some(e) if e.pos.map(|p| p.offset()) == Some(pos.offset()) => Some(ParseError {
+                expected: vec![name.to_string()],
+                ..e
+            }),
+            None if res.is_none() => Some(ParseError::new(Some(pos)).with_expected(name)),
+            e => e,
+        };
+        logger.error = outer;
+        match (&res, err) {
+            (None, Some(e)) => logger.fail(e),
+            (_, Some(e)) => logger.merge(e),
+            _ => (),
+        }
+        res
+    })
+}
+
 /// # Combinator: `inspect` (function ver.)
-fn inspect<'f, A: 'f, S: Clone + 'f>(
+fn inspect<'f, A: 'f, S: Stream + 'f>(
     p: impl Parsable<Stream = S, Result = A> + 'f,
 ) -> Parser<'f, (Option<A>, S), S> {
     Parser::new(move |stream, logger| {
@@ -50,7 +78,7 @@ fn inspect<'f, A: 'f, S: Clone + 'f>(
 }
 
 /// # Combinator: `recover` (function ver.)
-fn recover<'f, A: Clone + 'f, S: Clone>(
+fn recover<'f, A: Clone + 'f, S: Stream>(
     p: impl Parsable<Stream = S, Result = A> + 'f,
     x: A,
 ) -> Parser<'f, A, S> {
@@ -83,10 +111,42 @@ pub trait LogExt<'f, A: 'f, S>: Parsable<Stream = S, Result = A> {
         error(self, msg)
     }
 
+    /// # Combinator: `label`
+    ///
+    /// Name the parser in error messages. If the parser fails without
+    /// consuming any input, the expected items of its error are replaced
+    /// by `name`.
+    ///
+    /// # Example
+    /// ```
+    /// use parsic::combinators::*;
+    /// use parsic::core::Parsable;
+    /// use parsic::primitives::{char, satisfy, CharStream};
+    ///
+    /// let number = satisfy(|ch: &char| ch.is_ascii_digit()).some().label("number");
+    /// let parser = char('(').map(|_| vec![]).or(number);
+    ///
+    /// let mut st = CharStream::new("x");
+    /// let (res, logs) = parser.exec(&mut st);
+    ///
+    /// assert_eq!(None, res);
+    /// assert_eq!(
+    ///     "unexpected 'x', expected one of '(', number (row: 0, col: 0)",
+    ///     logs.error().unwrap().to_string()
+    /// );
+    /// ```
+    fn label(self, name: &'f str) -> Parser<'f, A, S>
+    where
+        S: Stream,
+        Self: Sized + 'f,
+    {
+        label(self, name)
+    }
+
     /// # Combinator: `inspect`
     fn inspect(self) -> Parser<'f, (Option<A>, S), S>
     where
-        S: Clone + 'f,
+        S: Stream + 'f,
         Self: Sized + 'f,
     {
         inspect(self)
@@ -96,7 +156,7 @@ pub trait LogExt<'f, A: 'f, S>: Parsable<Stream = S, Result = A> {
     fn recover(self, x: A) -> Parser<'f, A, S>
     where
         A: Clone,
-        S: Clone,
+        S: Stream,
         Self: Sized + 'f,
     {
         recover(self, x)
@@ -104,3 +164,78 @@ pub trait LogExt<'f, A: 'f, S>: Parsable<Stream = S, Result = A> {
 }
 
 impl<'f, A: 'f, S, P: Parsable<Stream = S, Result = A>> LogExt<'f, A, S> for P {}
+
+#[cfg(test)]
+mod test_error {
+    use crate::combinators::*;
+    use crate::core::{Msg, Parsable, Pos};
+    use crate::primitives::{char, literal, satisfy, CharStream};
+
+    #[test]
+    fn merge_expected_items() {
+        let ident = satisfy(|ch: &char| ch.is_alphabetic())
+            .some()
+            .label("identifier");
+        let number = satisfy(|ch: &char| ch.is_ascii_digit())
+            .some()
+            .label("number");
+        let parser = char('(').map(|_| vec![]).or(number).or(ident);
+
+        let mut st = CharStream::new("+1");
+        let (res, logs) = parser.exec(&mut st);
+
+        assert_eq!(None, res);
+        assert_eq!(1, logs.len());
+        match &logs.stack[0] {
+            Msg::Error(body) => assert_eq!(
+                "unexpected '+', expected one of '(', number, identifier",
+                body.msg
+            ),
+            _ => panic!("expecting an error"),
+        }
+    }
+
+    #[test]
+    fn furthest_error_wins() {
+        let parser = literal("ab")
+            .attempt()
+            .or(char('a').map(|_| "a"))
+            .and(char('c'));
+
+        let mut st = CharStream::new("ax");
+        let (res, logs) = parser.exec(&mut st);
+
+        assert_eq!(None, res);
+        let err = logs.error().unwrap();
+        assert_eq!(Some(1), err.pos.map(|p: Pos| p.offset()));
+        assert_eq!(vec!["'c'".to_string()], err.expected);
+    }
+
+    #[test]
+    fn many_merges_expected_items() {
+        let parser = char('a').many().and(char('b'));
+
+        let mut st = CharStream::new("aac");
+        let (res, logs) = parser.exec(&mut st);
+
+        assert_eq!(None, res);
+        assert_eq!(
+            "unexpected 'c', expected one of 'a', 'b' (row: 0, col: 2)",
+            logs.error().unwrap().to_string()
+        );
+    }
+
+    #[test]
+    fn label_keeps_consumed_errors() {
+        let parser = char('a').and(char('b')).label("ab");
+
+        let mut st = CharStream::new("ac");
+        let (res, logs) = parser.exec(&mut st);
+
+        assert_eq!(None, res);
+        assert_eq!(
+            "unexpected 'c', expected 'b' (row: 0, col: 1)",
+            logs.error().unwrap().to_string()
+        );
+    }
+}
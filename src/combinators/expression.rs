@@ -0,0 +1,293 @@
+use crate::combinators::*;
+use crate::core::{return_none, Parsable, ParseError, ParseLogger, Parser, Stream};
+use std::rc::Rc;
+
+type Unary<'f, A> = Rc<dyn Fn(A) -> A + 'f>;
+type Binary<'f, A> = Rc<dyn Fn(A, A) -> A + 'f>;
+type UnaryOp<'f, A, S> = (Parser<'f, (), S>, usize, Unary<'f, A>);
+type BinaryOp<'f, A, S> = (Parser<'f, (), S>, Assoc, usize, Binary<'f, A>);
+
+/// # Enum `Assoc`
+/// Associativity of infix operators.
+///
+/// - `Left`: `a - b - c` is `(a - b) - c`
+/// - `Right`: `a ^ b ^ c` is `a ^ (b ^ c)`
+/// - `None`: `a == b == c` is an error
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Assoc {
+    Left,
+    Right,
+    None,
+}
+
+/// # Struct `Expression`
+/// Builder of expression parsers from an atom parser and a table of
+/// prefix, postfix and infix operators. Each operator has a binding
+/// power: the higher it is, the tighter the operator binds. The parser
+/// is built by `build` and parses expressions by precedence climbing.
+///
+/// Operators are tried in the order they are added. As with `or`, an
+/// operator that fails after consuming input is not backtracked, so
+/// wrap such operators with `attempt`.
+pub struct Expression<'f, A, S> {
+    atom: Parser<'f, A, S>,
+    prefix: Vec<UnaryOp<'f, A, S>>,
+    postfix: Vec<UnaryOp<'f, A, S>>,
+    infix: Vec<BinaryOp<'f, A, S>>,
+}
+
+/// # Combinator: `expression`
+///
+/// Create an `Expression` builder with given atom parser, e.g. numbers,
+/// identifiers or parenthesized expressions.
+///
+/// # Example
+/// ```
+/// use parsic::combinators::*;
+/// use parsic::core::Parsable;
+/// use parsic::primitives::{char, satisfy, CharStream};
+///
+/// let num = satisfy(|ch: &char| ch.is_ascii_digit()).map(|ch| ch as i64 - '0' as i64);
+/// let parser = expression(num)
+///     .infix(char('+'), Assoc::Left, 10, |x, y| x + y)
+///     .infix(char('-'), Assoc::Left, 10, |x, y| x - y)
+///     .infix(char('*'), Assoc::Left, 20, |x, y| x * y)
+///     .infix(char('^'), Assoc::Right, 30, |x, y| x.pow(y as u32))
+///     .prefix(char('-'), 25, |x| -x)
+///     .build();
+///
+/// let mut st = CharStream::new("1-2*-2^3^0-4");
+/// let (res, logs) = parser.exec(&mut st);
+///
+/// assert_eq!(Some(1 - 2 * -(2i64.pow(3u32.pow(0))) - 4), res);
+/// assert_eq!("", st.as_str());
+/// assert_eq!(0, logs.len());
+/// ```
+pub fn expression<'f, A: 'f, S>(
+    atom: impl Parsable<Stream = S, Result = A> + 'f,
+) -> Expression<'f, A, S> {
+    Expression {
+        atom: atom.into_parser(),
+        prefix: vec![],
+        postfix: vec![],
+        infix: vec![],
+    }
+}
+
+impl<'f, A: 'f, S: Stream + 'f> Expression<'f, A, S> {
+    /// Add a prefix operator, whose operand binds at least as tight as `bp`
+    pub fn prefix<B: 'f>(
+        mut self,
+        op: impl Parsable<Stream = S, Result = B> + 'f,
+        bp: usize,
+        f: impl Fn(A) -> A + 'f,
+    ) -> Self {
+        self.prefix.push((op.map(|_| ()), bp, Rc::new(f)));
+        self
+    }
+
+    /// Add a postfix operator
+    pub fn postfix<B: 'f>(
+        mut self,
+        op: impl Parsable<Stream = S, Result = B> + 'f,
+        bp: usize,
+        f: impl Fn(A) -> A + 'f,
+    ) -> Self {
+        self.postfix.push((op.map(|_| ()), bp, Rc::new(f)));
+        self
+    }
+
+    /// Add an infix operator
+    pub fn infix<B: 'f>(
+        mut self,
+        op: impl Parsable<Stream = S, Result = B> + 'f,
+        assoc: Assoc,
+        bp: usize,
+        f: impl Fn(A, A) -> A + 'f,
+    ) -> Self {
+        self.infix.push((op.map(|_| ()), assoc, bp, Rc::new(f)));
+        self
+    }
+
+    /// Build the expression parser
+    pub fn build(self) -> Parser<'f, A, S> {
+        // Operators of each kind are merged into a single parser yielding their index
+        fn choice<'f, S: Stream + 'f>(ops: Vec<Parser<'f, (), S>>) -> Parser<'f, usize, S> {
+            ops.into_iter()
+                .enumerate()
+                .map(|(i, op)| op.map(move |_| i))
+                .reduce(|acc, op| acc.or(op))
+                .unwrap_or_else(empty)
+        }
+
+        let prefix = choice(self.prefix.iter().map(|x| x.0.clone()).collect());
+        let postfix = choice(self.postfix.iter().map(|x| x.0.clone()).collect());
+        let infix = choice(self.infix.iter().map(|x| x.0.clone()).collect());
+        let table = Rc::new(Table {
+            operand: prefix.map(Operand::Prefix).or(self.atom.map(Operand::Atom)),
+            op: postfix.map(Op::Postfix).or(infix.map(Op::Infix)),
+            prefix: self.prefix.into_iter().map(|(_, bp, f)| (bp, f)).collect(),
+            postfix: self.postfix.into_iter().map(|(_, bp, f)| (bp, f)).collect(),
+            infix: self
+                .infix
+                .into_iter()
+                .map(|(_, a, bp, f)| (a, bp, f))
+                .collect(),
+        });
+        Parser::new(move |stream: &mut S, logger| table.parse(stream, logger, 0))
+    }
+}
+
+enum Operand<A> {
+    Prefix(usize),
+    Atom(A),
+}
+
+enum Op {
+    Postfix(usize),
+    Infix(usize),
+}
+
+/// Operator table of a built expression parser
+struct Table<'f, A, S> {
+    operand: Parser<'f, Operand<A>, S>,
+    op: Parser<'f, Op, S>,
+    prefix: Vec<(usize, Unary<'f, A>)>,
+    postfix: Vec<(usize, Unary<'f, A>)>,
+    infix: Vec<(Assoc, usize, Binary<'f, A>)>,
+}
+
+impl<'f, A: 'f, S: Stream + 'f> Table<'f, A, S> {
+    /// Parse an expression whose operators bind at least as tight as `min_bp`
+    fn parse(&self, stream: &mut S, logger: &mut ParseLogger, min_bp: usize) -> Option<A> {
+        let st = stream.clone();
+        self.climb(stream, logger, min_bp)
+            .or_else(|| return_none(stream, &st, logger))
+    }
+
+    fn climb(&self, stream: &mut S, logger: &mut ParseLogger, min_bp: usize) -> Option<A> {
+        let mut lhs = match self.operand.parse(stream, logger)? {
+            Operand::Prefix(i) => {
+                let (bp, f) = &self.prefix[i];
+                f(self.parse(stream, logger, *bp)?)
+            }
+            Operand::Atom(x) => x,
+        };
+        let mut non_assoc = None;
+
+        loop {
+            let (bak, lg) = (stream.clone(), logger.clone());
+            let outer = logger.enter();
+            let op = self.op.parse(stream, logger);
+            let committed =
+                logger.is_committed() || logger.has_consumed(bak.index(), stream.index());
+            logger.leave(outer, op.is_some());
+
+            let bp = match &op {
+                Some(Op::Postfix(i)) => self.postfix[*i].0,
+                Some(Op::Infix(i)) => self.infix[*i].1,
+                None if committed => return None,
+                None => min_bp,
+            };
+            if op.is_none() || bp < min_bp {
+                // Leave the operator to outer expressions
+                *stream = bak;
+                logger.restore(lg);
+                break;
+            }
+
+            match op {
+                Some(Op::Postfix(i)) => lhs = (self.postfix[i].1)(lhs),
+                Some(Op::Infix(i)) => {
+                    let (assoc, bp, f) = &self.infix[i];
+                    if *assoc == Assoc::None && non_assoc == Some(*bp) {
+                        logger.fatal(
+                            ParseError::new(Some(bak.pos()))
+                                .with_unexpected("chained non-associative operator")
+                                .with_expected("parentheses around either operand"),
+                        );
+                        return None;
+                    }
+                    let next = match assoc {
+                        Assoc::Right => *bp,
+                        _ => bp + 1,
+                    };
+                    lhs = f(lhs, self.parse(stream, logger, next)?);
+                    non_assoc = Some(*bp).filter(|_| *assoc == Assoc::None);
+                }
+                None => unreachable!(),
+            }
+        }
+        Some(lhs)
+    }
+}
+
+#[cfg(test)]
+mod test_expression {
+    use crate::combinators::*;
+    use crate::core::{Parsable, Parser};
+    use crate::primitives::{char, literal, satisfy, CharStream, PrimitiveExt};
+
+    /// Parse an expression into a fully parenthesized string
+    fn parser<'f>() -> Parser<'f, String, CharStream<'f>> {
+        fix(|expr| {
+            let atom = satisfy(|ch: &char| ch.is_alphanumeric())
+                .map(|ch| ch.to_string())
+                .or(char('(').mid(expr, char(')')))
+                .trim();
+            let bin = |op: &'static str| move |x, y| format!("({} {} {})", x, op, y);
+            expression(atom)
+                .infix(literal("==").trim(), Assoc::None, 5, bin("=="))
+                .infix(char('+').trim(), Assoc::Left, 10, bin("+"))
+                .infix(char('-').trim(), Assoc::Left, 10, bin("-"))
+                .infix(char('*').trim(), Assoc::Left, 20, bin("*"))
+                .infix(char('^').trim(), Assoc::Right, 30, bin("^"))
+                .prefix(char('-').trim(), 25, |x| format!("(-{})", x))
+                .postfix(char('!').trim(), 40, |x| format!("({}!)", x))
+                .build()
+        })
+    }
+
+    fn parse(inp: &str) -> (Option<String>, String) {
+        let mut st = CharStream::new(inp);
+        let (res, _) = parser().exec(&mut st);
+        (res, st.as_str().to_string())
+    }
+
+    #[test]
+    fn precedence_and_associativity() {
+        assert_eq!(
+            Some("((a + (b * c)) - d)".to_string()),
+            parse("a + b * c - d").0
+        );
+        assert_eq!(Some("(a ^ (b ^ c))".to_string()), parse("a^b^c").0);
+        assert_eq!(Some("((-(a ^ b)) * c)".to_string()), parse("-a^b*c").0);
+        assert_eq!(Some("(-((a!) ^ 2))".to_string()), parse("-a!^2").0);
+        assert_eq!(
+            Some("((a + b) == (c * d))".to_string()),
+            parse("(a+b) == c*d").0
+        );
+    }
+
+    #[test]
+    fn non_associative() {
+        let mut st = CharStream::new("a == b == c");
+        let (res, logs) = parser().exec(&mut st);
+
+        assert_eq!(None, res);
+        assert_eq!("a == b == c", st.as_str());
+        assert_eq!(1, logs.len());
+        assert_eq!(
+            "unexpected chained non-associative operator, \
+             expected parentheses around either operand (row: 0, col: 7)",
+            logs.error().unwrap().to_string()
+        );
+        assert_eq!(Some(7), logs.error().unwrap().pos.map(|p| p.offset()));
+    }
+
+    #[test]
+    fn fail_with_grace() {
+        assert_eq!((None, "a + ".to_string()), parse("a + "));
+        assert_eq!((Some("a".to_string()), "]".to_string()), parse("a]"));
+    }
+}
@@ -1,8 +1,10 @@
-use crate::core::{Parsable, ParseLogger, Parser};
-use std::rc::Rc;
+use crate::core::{Msg, MsgBody, Parsable, ParseLogger, Parser, Stream};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::{Rc, Weak};
 
-type FixFn<'f, A, S> = dyn Fn(Parser<'f, A, S>) -> Parser<'f, A, S> + 'f;
-pub struct Fix<'f, A, S>(Rc<FixFn<'f, A, S>>);
+/// The body of a recursive parser, built once by `fix`.
+pub struct Fix<'f, A, S>(Rc<RefCell<Option<Parser<'f, A, S>>>>);
 
 impl<'f, A, S> Clone for Fix<'f, A, S> {
     fn clone(&self) -> Self {
@@ -15,7 +17,7 @@ impl<'f, A: 'f, S: 'f> Parsable for Fix<'f, A, S> {
     type Result = A;
     fn parse(&self, stream: &mut S, logger: &mut ParseLogger) -> Option<A> {
         //! fix f = f (fix f)
-        (self.0)(self.clone().into_parser()).parse(stream, logger)
+        self.0.borrow().as_ref()?.parse(stream, logger)
     }
 }
 
@@ -31,6 +33,11 @@ impl<'f, A: 'f, S: 'f> Parsable for Fix<'f, A, S> {
 /// ```plain
 /// fix f = f (fix f)
 /// ```
+///
+/// The body `f` is called only once: the parser it receives refers back
+/// to the parser it returns, so stateful parsers like `memo` built inside
+/// the body are shared by all recursive calls.
+///
 /// # Example
 /// ```
 /// use parsic::combinators::*;
@@ -51,14 +58,185 @@ pub fn fix<'f, A: 'f, F, S: 'f>(fix: F) -> Parser<'f, A, S>
 where
     F: Fn(Parser<'f, A, S>) -> Parser<'f, A, S> + 'f,
 {
-    Fix(Rc::new(fix)).into_parser()
+    let body = Rc::new(RefCell::new(None));
+    // Refer to the body weakly to avoid a reference cycle
+    let weak = Rc::downgrade(&body);
+    let rec = Parser::new(move |stream: &mut S, logger| {
+        Fix(upgrade(&weak, logger, "fix")?).parse(stream, logger)
+    });
+    let parser = fix(rec);
+    *body.borrow_mut() = Some(parser);
+    Fix(body).into_parser()
+}
+
+/// Helper function that returns the body of a recursive parser. The
+/// recursive handle only refers to the body weakly, so if the handle
+/// outlives the parser returned by `name`, a fatal error is logged.
+fn upgrade<T>(weak: &Weak<T>, logger: &mut ParseLogger, name: &str) -> Option<Rc<T>> {
+    let body = weak.upgrade();
+    if body.is_none() {
+        let msg = format!(
+            "the recursive parser of `{}` is applied after `{}` was dropped",
+            name, name
+        );
+        logger.add(Msg::Error(MsgBody::new(&msg, None)));
+        logger.cut = true;
+    }
+    body
+}
+
+/// Seeds of left-recursive calls in progress, keyed by stream index
+type Seeds<A, S> = RefCell<HashMap<usize, Option<(A, S)>>>;
+
+/// # Combinator: `fix_left_rec`
+///
+/// A variant of `fix` that supports left-recursive grammars like
+/// `expr := expr '-' num | num`, which would make `fix` recurse forever.
+///
+/// A left-recursive call, i.e. a call at the same position while the rule
+/// is still being parsed there, fails at first. The rule is then parsed
+/// again and again, each time left-recursive calls returning the result of
+/// the previous round (the *seed*), for as long as it consumes more input.
+/// Indirect left recursion through other `fix_left_rec` rules is supported.
+///
+/// Left-recursive calls do not count as consuming input, so alternatives
+/// after a failed `expr '+' ...` are still tried.
+///
+/// # Example
+/// ```
+/// use parsic::combinators::*;
+/// use parsic::core::Parsable;
+/// use parsic::primitives::{char, satisfy, CharStream};
+///
+/// // expr := expr '-' num | num
+/// let num = satisfy(|ch: &char| ch.is_ascii_digit()).map(|ch| ch as i64 - '0' as i64);
+/// let parser = fix_left_rec(move |expr| {
+///     expr.left(char('-')).and(num.clone()).map(|(x, y)| x - y).or(num.clone())
+/// });
+///
+/// let mut st = CharStream::new("9-3-2");
+/// let (res, logs) = parser.exec(&mut st);
+///
+/// assert_eq!(Some(4), res);
+/// assert_eq!("", st.as_str());
+/// assert_eq!(0, logs.len());
+/// ```
+pub fn fix_left_rec<'f, A: Clone + 'f, F, S: Stream + 'f>(fix: F) -> Parser<'f, A, S>
+where
+    F: Fn(Parser<'f, A, S>) -> Parser<'f, A, S> + 'f,
+{
+    let body = Rc::new(RefCell::new(None));
+    let seeds: Rc<Seeds<A, S>> = Rc::default();
+    let (weak, rec_seeds) = (Rc::downgrade(&body), seeds.clone());
+    let rec = Parser::new(move |stream: &mut S, logger| {
+        let body = upgrade(&weak, logger, "fix_left_rec")?;
+        grow(&Fix(body), &rec_seeds, stream, logger)
+    });
+    let parser = fix(rec);
+    *body.borrow_mut() = Some(parser);
+    Parser::new(move |stream: &mut S, logger| grow(&Fix(body.clone()), &seeds, stream, logger))
+}
+
+/// Helper function that parses a left-recursive rule by growing its seed
+fn grow<A: Clone, S: Stream>(
+    p: &impl Parsable<Stream = S, Result = A>,
+    seeds: &Seeds<A, S>,
+    stream: &mut S,
+    logger: &mut ParseLogger,
+) -> Option<A> {
+    let key = stream.index();
+    if let Some(seed) = seeds.borrow().get(&key) {
+        // A left-recursive call: replay the seed
+        return seed.clone().map(|(x, st)| {
+            logger.replay(key, st.index());
+            *stream = st;
+            x
+        });
+    }
+
+    seeds.borrow_mut().insert(key, None);
+    let (mut best, mut best_lg) = (None::<(A, S)>, None);
+    let last = loop {
+        // Results memoized in previous rounds may depend on older seeds
+        let (mut st, mut lg) = (stream.clone(), logger.next_round());
+        let res = p.parse(&mut st, &mut lg);
+        // Replays of the seed only matter within the round
+        lg.replayed.clear();
+        let end = best.as_ref().map_or(key, |(_, s)| s.index());
+        match res {
+            Some(x) if st.index() > end || best_lg.is_none() => {
+                best = Some((x, st));
+                best_lg = Some(lg);
+                seeds.borrow_mut().insert(key, best.clone());
+            }
+            _ => break lg,
+        }
+    };
+    seeds.borrow_mut().remove(&key);
+
+    match (best, best_lg) {
+        (Some((x, st)), Some(lg)) => {
+            *stream = st;
+            logger.absorb(lg, true);
+            // Keep errors of the round that stopped growing
+            if let Some(err) = last.error {
+                logger.merge(err);
+            }
+            Some(x)
+        }
+        _ => {
+            logger.absorb(last, false);
+            None
+        }
+    }
 }
 
 #[cfg(test)]
 mod test {
     use crate::combinators::*;
-    use crate::core::Parsable;
+    use crate::core::{Msg, Parsable, Parser};
     use crate::primitives::{char, satisfy, CharStream};
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    #[test]
+    fn fix_dropped_before_handle() {
+        let handle = Rc::new(RefCell::new(None::<Parser<char, CharStream>>));
+        let inner = handle.clone();
+        drop(fix(move |rec| {
+            *inner.borrow_mut() = Some(rec.clone());
+            char('1').right(rec).or(char('0'))
+        }));
+        let parser = handle.borrow_mut().take().unwrap().or(char('1'));
+
+        let mut st = CharStream::new("1");
+        let (res, logs) = parser.exec(&mut st);
+
+        assert_eq!(None, res);
+        assert!(matches!(
+            &logs.stack[..],
+            [Msg::Error(body)] if body.msg == "the recursive parser of `fix` is applied after `fix` was dropped"
+        ));
+    }
+
+    #[test]
+    fn fix_left_rec_dropped_before_handle() {
+        let handle = Rc::new(RefCell::new(None::<Parser<char, CharStream>>));
+        let inner = handle.clone();
+        drop(fix_left_rec(move |rec| {
+            *inner.borrow_mut() = Some(rec.clone());
+            rec.left(char('1')).or(char('0'))
+        }));
+        let parser = handle.borrow_mut().take().unwrap();
+
+        let (res, logs) = parser.exec(&mut CharStream::new("01"));
+
+        assert_eq!(None, res);
+        assert!(matches!(
+            &logs.stack[..],
+            [Msg::Error(body)] if body.msg.contains("`fix_left_rec` was dropped")
+        ));
+    }
 
     #[test]
     fn mutual_recursive_grammar() {
@@ -68,7 +246,7 @@ mod test {
         // uint     := digit { digit }
         // digit    := '0' | '1' | ... | '9'
         let expr = fix(move |expr| {
-            let digit = satisfy(|&ch| ch.is_digit(10));
+            let digit = satisfy(|&ch: &char| ch.is_ascii_digit());
             let uint = digit
                 .some()
                 .map_result(|v| v.iter().collect::<String>().parse::<u64>());
@@ -80,6 +258,7 @@ mod test {
                     .left(char('*'))
                     .and(term.clone())
                     .map(|(v1, v2)| v1 * v2)
+                    .attempt()
                     .or(factor.clone())
             });
 
@@ -87,6 +266,7 @@ mod test {
                 .left(char('+'))
                 .and(expr)
                 .map(|(v1, v2)| v1 + v2)
+                .attempt()
                 .or(term)
         });
 
@@ -96,4 +276,88 @@ mod test {
         assert_eq!(Some(15), res);
         assert_eq!(0, logs.len());
     }
+
+    #[test]
+    fn direct_left_recursion() {
+        // expr := expr '+' num | expr '-' num | num
+        let num = satisfy(|&ch: &char| ch.is_ascii_digit()).map(|ch| ch as i64 - '0' as i64);
+        let expr = fix_left_rec(move |expr| {
+            let op = |ch| expr.clone().left(char(ch)).and(num.clone());
+            op('+')
+                .map(|(x, y)| x + y)
+                .or(op('-').map(|(x, y)| x - y))
+                .or(num.clone())
+        });
+
+        let mut st = CharStream::new("9-3+1-2!");
+        let (res, logs) = expr.exec(&mut st);
+
+        assert_eq!(Some(5), res);
+        assert_eq!("!", st.as_str());
+        assert_eq!(0, logs.len());
+        assert_eq!(
+            "unexpected '!', expected one of '+', '-' (row: 0, col: 7)",
+            logs.error().unwrap().to_string()
+        );
+    }
+
+    #[test]
+    fn left_recursion_with_memo() {
+        let num = || satisfy(|&ch: &char| ch.is_ascii_digit()).map(|ch| ch as i64 - '0' as i64);
+        let expr = fix_left_rec(|expr| {
+            expr.left(char('-'))
+                .and(num())
+                .map(|(x, y)| x - y)
+                .or(num())
+                .memo()
+        });
+        assert_eq!(Some(4), expr.exec(&mut CharStream::new("9-3-2")).0);
+
+        let expr = fix_left_rec(|expr| {
+            expr.memo()
+                .left(char('-'))
+                .and(num())
+                .map(|(x, y)| x - y)
+                .or(num())
+        });
+        assert_eq!(Some(4), expr.exec(&mut CharStream::new("9-3-2")).0);
+    }
+
+    #[test]
+    fn indirect_left_recursion() {
+        // a := b 'x' | 'a'
+        // b := a 'y' | 'b'
+        let a = fix_left_rec(|a| {
+            let b = fix_left_rec(move |_| {
+                a.clone()
+                    .left(char('y'))
+                    .map(|s| s + "y")
+                    .or(char('b').map(|_| "b".to_string()))
+            });
+            b.left(char('x'))
+                .map(|s| s + "x")
+                .or(char('a').map(|_| "a".to_string()))
+        });
+
+        let mut st = CharStream::new("bxyxyx");
+        let (res, logs) = a.exec(&mut st);
+
+        assert_eq!(Some("bxyxyx".to_string()), res);
+        assert_eq!(0, logs.len());
+
+        let mut st = CharStream::new("ayx");
+        assert_eq!(Some("ayx".to_string()), a.exec(&mut st).0);
+    }
+
+    #[test]
+    fn left_recursion_fail_with_grace() {
+        let expr = fix_left_rec(|expr| expr.left(char('+')).or(char('1')));
+
+        let mut st = CharStream::new("2+");
+        let (res, logs) = expr.exec(&mut st);
+
+        assert_eq!(None, res);
+        assert_eq!("2+", st.as_str());
+        assert_eq!(1, logs.len());
+    }
 }
@@ -1,4 +1,4 @@
-use crate::core::{return_none, Msg, MsgBody, Parsable, Parser};
+use crate::core::{return_none, Msg, MsgBody, Parsable, Parser, Span, Spanned, Stream};
 
 /// # Combinator: `map` (function ver.)
 ///
@@ -28,15 +28,15 @@ use crate::core::{return_none, Msg, MsgBody, Parsable, Parser};
 /// assert_eq!("ello", st.as_str());
 /// assert_eq!(0, logs.len());
 /// ```
-pub fn map<'f, A: 'f, B: 'f, S: Clone>(
+pub fn map<'f, A: 'f, B: 'f, S: Stream>(
     p: impl Parsable<Stream = S, Result = A> + 'f,
     f: impl Fn(A) -> B + 'f,
 ) -> Parser<'f, B, S> {
     Parser::new(move |stream: &mut S, logger| {
         let st = stream.clone();
         p.parse(stream, logger)
-            .map(|x| f(x))
-            .or_else(|| return_none(stream, &st))
+            .map(&f)
+            .or_else(|| return_none(stream, &st, logger))
     })
 }
 
@@ -53,7 +53,7 @@ pub fn map<'f, A: 'f, B: 'f, S: Clone>(
 /// use parsic::core::Parsable;
 /// use parsic::primitives::{CharStream, satisfy};
 ///
-/// let parser = map_option(satisfy(|_| true), |ch: char| ch.to_digit(10));
+/// let parser = map_option(satisfy(|_: &char| true), |ch: char| ch.to_digit(10));
 ///
 /// let mut st = CharStream::new("817");
 /// let (res, logs) = parser.exec(&mut st);
@@ -62,18 +62,18 @@ pub fn map<'f, A: 'f, B: 'f, S: Clone>(
 /// assert_eq!("17", st.as_str());
 /// assert_eq!(0, logs.len());
 /// ```
-pub fn map_option<'f, A: 'f, B: 'f, S: Clone>(
+pub fn map_option<'f, A: 'f, B: 'f, S: Stream>(
     p: impl Parsable<Stream = S, Result = A> + 'f,
     f: impl Fn(A) -> Option<B> + 'f,
 ) -> Parser<'f, B, S> {
     Parser::new(move |stream: &mut S, logger| {
         let st = stream.clone();
-        p.parse(stream, logger).and_then(|x| f(x)).or_else(|| {
+        p.parse(stream, logger).and_then(&f).or_else(|| {
             logger.add(Msg::Error(MsgBody::new(
                 "map_option recieved a function that yielded None.",
                 None,
             )));
-            return_none(stream, &st)
+            return_none(stream, &st, logger)
         })
     })
 }
@@ -94,7 +94,7 @@ pub fn map_option<'f, A: 'f, B: 'f, S: Clone>(
 ///
 /// // A parser that consumes a natural number
 /// let parser = map_result(
-///                 some(satisfy(|&ch| ch.is_digit(10))),
+///                 some(satisfy(|&ch: &char| ch.is_ascii_digit())),
 ///                 |v| v.into_iter().collect::<String>().parse::<i64>()
 ///              );
 ///
@@ -105,7 +105,7 @@ pub fn map_option<'f, A: 'f, B: 'f, S: Clone>(
 /// assert_eq!("", st.as_str());
 /// assert_eq!(0, logs.len());
 /// ```
-pub fn map_result<'f, A: 'f, B: 'f, E: ToString, S: Clone>(
+pub fn map_result<'f, A: 'f, B: 'f, E: ToString, S: Stream>(
     p: impl Parsable<Stream = S, Result = A> + 'f,
     f: impl Fn(A) -> Result<B, E> + 'f,
 ) -> Parser<'f, B, S> {
@@ -115,12 +115,113 @@ pub fn map_result<'f, A: 'f, B: 'f, E: ToString, S: Clone>(
             Ok(r) => Some(r),
             Err(e) => {
                 logger.add(Msg::Error(MsgBody::new(&e.to_string()[..], None)));
-                return_none(stream, &st)
+                return_none(stream, &st, logger)
             }
         })
     })
 }
 
+/// # Combinator: `spanned` (function ver.)
+///
+/// Wraps the result of a parser along with the span of input it consumed.
+///
+/// # Example
+/// ```
+/// use parsic::combinators::*;
+/// use parsic::core::Parsable;
+/// use parsic::primitives::{char, literal, CharStream};
+///
+/// let parser = char(' ').many().right(spanned(literal("Hello")));
+///
+/// let mut st = CharStream::new("  Hello!");
+/// let (res, logs) = parser.exec(&mut st);
+///
+/// let hello = res.unwrap();
+/// assert_eq!("Hello", hello.value);
+/// assert_eq!(2..7, hello.span.range());
+/// assert_eq!(0, logs.len());
+/// ```
+pub fn spanned<'f, A: 'f, S: Stream>(
+    p: impl Parsable<Stream = S, Result = A> + 'f,
+) -> Parser<'f, Spanned<A>, S> {
+    Parser::new(move |stream: &mut S, logger| {
+        let (start, idx) = (stream.pos(), stream.index());
+        let value = p.parse(stream, logger)?;
+        let end = match stream.index() == idx {
+            true => start,
+            false => stream.end_pos(),
+        };
+        Some(Spanned::new(value, Span::new(start, end)))
+    })
+}
+
+/// # Combinator: `recognize` (function ver.)
+///
+/// Returns the exact input consumed by a parser instead of its result,
+/// borrowed from the input. Works on any `Stream` whose remaining input
+/// is a `&str`, e.g. `CharStream`.
+///
+/// # Example
+/// ```
+/// use parsic::combinators::*;
+/// use parsic::core::Parsable;
+/// use parsic::primitives::{char, digit, CharStream};
+///
+/// // Validate a decimal number but keep its text
+/// let parser = recognize(char('-').optional().and(digit()).and(char('.').and(digit()).optional()));
+///
+/// let mut st = CharStream::new("-3.14 ");
+/// let (res, logs) = parser.exec(&mut st);
+///
+/// assert_eq!(Some("-3.14"), res);
+/// assert_eq!(" ", st.as_str());
+/// assert_eq!(0, logs.len());
+/// ```
+pub fn recognize<'f, A: 'f, S>(
+    p: impl Parsable<Stream = S, Result = A> + 'f,
+) -> Parser<'f, &'f str, S>
+where
+    S: Stream<Slice = &'f str>,
+{
+    Parser::new(move |stream: &mut S, logger| {
+        let inp = stream.as_slice();
+        p.parse(stream, logger)?;
+        Some(&inp[..inp.len() - stream.as_slice().len()])
+    })
+}
+
+/// # Combinator: `with_recognized` (function ver.)
+///
+/// Same as `recognize`, but returns the result of the parser as well.
+///
+/// # Example
+/// ```
+/// use parsic::combinators::*;
+/// use parsic::core::Parsable;
+/// use parsic::primitives::{char, CharStream};
+///
+/// let parser = with_recognized(char('a').many());
+///
+/// let mut st = CharStream::new("aab");
+/// let (res, logs) = parser.exec(&mut st);
+///
+/// assert_eq!(Some((vec!['a', 'a'], "aa")), res);
+/// assert_eq!(0, logs.len());
+/// ```
+pub fn with_recognized<'f, A: 'f, S>(
+    p: impl Parsable<Stream = S, Result = A> + 'f,
+) -> Parser<'f, (A, &'f str), S>
+where
+    S: Stream<Slice = &'f str>,
+{
+    Parser::new(move |stream: &mut S, logger| {
+        let inp = stream.as_slice();
+        let value = p.parse(stream, logger)?;
+        let len = inp.len() - stream.as_slice().len();
+        Some((value, &inp[..len]))
+    })
+}
+
 pub trait FunctorExt<'f, A: 'f, S>: Parsable<Stream = S, Result = A> {
     /// # Combinator: `map`
     ///
@@ -153,7 +254,7 @@ pub trait FunctorExt<'f, A: 'f, S>: Parsable<Stream = S, Result = A> {
     /// ```
     fn map<B: 'f>(self, f: impl Fn(A) -> B + 'f) -> Parser<'f, B, S>
     where
-        S: Clone,
+        S: Stream,
         Self: Sized + 'f,
     {
         map(self, f)
@@ -172,7 +273,7 @@ pub trait FunctorExt<'f, A: 'f, S>: Parsable<Stream = S, Result = A> {
     /// use parsic::core::Parsable;
     /// use parsic::primitives::{CharStream, satisfy};
     ///
-    /// let parser = satisfy(|_| true).map_option(|ch: char| ch.to_digit(10));
+    /// let parser = satisfy(|_: &char| true).map_option(|ch: char| ch.to_digit(10));
     ///
     /// let mut st = CharStream::new("817");
     /// let (res, logs) = parser.exec(&mut st);
@@ -183,7 +284,7 @@ pub trait FunctorExt<'f, A: 'f, S>: Parsable<Stream = S, Result = A> {
     /// ```
     fn map_option<B: 'f>(self, f: impl Fn(A) -> Option<B> + 'f) -> Parser<'f, B, S>
     where
-        S: Clone,
+        S: Stream,
         Self: Sized + 'f,
     {
         map_option(self, f)
@@ -204,7 +305,7 @@ pub trait FunctorExt<'f, A: 'f, S>: Parsable<Stream = S, Result = A> {
     /// use parsic::primitives::{CharStream, satisfy};
     ///
     /// // A parser that consumes a natural number
-    /// let parser = satisfy(|&ch| ch.is_digit(10)).some()
+    /// let parser = satisfy(|&ch: &char| ch.is_ascii_digit()).some()
     ///              .map_result(|v| v.into_iter().collect::<String>().parse::<i64>());
     ///
     /// let mut st = CharStream::new("12345");
@@ -217,11 +318,75 @@ pub trait FunctorExt<'f, A: 'f, S>: Parsable<Stream = S, Result = A> {
     fn map_result<B: 'f, E>(self, f: impl Fn(A) -> Result<B, E> + 'f) -> Parser<'f, B, S>
     where
         E: ToString,
-        S: Clone,
+        S: Stream,
         Self: Sized + 'f,
     {
         map_result(self, f)
     }
+
+    /// # Combinator: `spanned`
+    ///
+    /// Wraps the result of current parser along with the span of input it consumed.
+    ///
+    /// # Example
+    /// ```
+    /// use parsic::combinators::*;
+    /// use parsic::core::Parsable;
+    /// use parsic::primitives::{char, CharStream};
+    ///
+    /// let parser = char('a').many().spanned();
+    ///
+    /// let mut st = CharStream::new("aaab");
+    /// let (res, logs) = parser.exec(&mut st);
+    ///
+    /// let span = res.unwrap().span;
+    /// assert_eq!((0, 3), (span.start.offset(), span.end.offset()));
+    /// assert_eq!(0, logs.len());
+    /// ```
+    fn spanned(self) -> Parser<'f, Spanned<A>, S>
+    where
+        S: Stream,
+        Self: Sized + 'f,
+    {
+        spanned(self)
+    }
+
+    /// # Combinator: `recognize`
+    ///
+    /// Returns the exact input consumed by current parser instead of its result.
+    ///
+    /// # Example
+    /// ```
+    /// use parsic::combinators::*;
+    /// use parsic::core::Parsable;
+    /// use parsic::primitives::{alpha, alnum, char, CharStream};
+    ///
+    /// let ident = alpha().or(char('_').recognize()).and(alnum().many()).recognize();
+    ///
+    /// let mut st = CharStream::new("_x86 = 1");
+    /// let (res, logs) = ident.exec(&mut st);
+    ///
+    /// assert_eq!(Some("_x86"), res);
+    /// assert_eq!(0, logs.len());
+    /// ```
+    fn recognize(self) -> Parser<'f, &'f str, S>
+    where
+        S: Stream<Slice = &'f str>,
+        Self: Sized + 'f,
+    {
+        recognize(self)
+    }
+
+    /// # Combinator: `with_recognized`
+    ///
+    /// Returns the result of current parser along with the exact input it consumed.
+    fn with_recognized(self) -> Parser<'f, (A, &'f str), S>
+    where
+        S: Stream<Slice = &'f str>,
+        Self: Sized + 'f,
+    {
+        with_recognized(self)
+    }
 }
 
 impl<'f, A: 'f, S, P: Parsable<Stream = S, Result = A>> FunctorExt<'f, A, S> for P {}
@@ -246,7 +411,7 @@ mod test_functor {
 
     #[test]
     fn map_option_fail_with_grace() {
-        let parser = satisfy(|_| true).map_option(|ch: char| ch.to_digit(10));
+        let parser = satisfy(|_: &char| true).map_option(|ch: char| ch.to_digit(10));
 
         let mut st = CharStream::new("naive");
         let (res, logs) = parser.exec(&mut st);
@@ -258,7 +423,7 @@ mod test_functor {
 
     #[test]
     fn map_result_fail_with_grace() {
-        let parser = satisfy(|&ch| ch.is_digit(10))
+        let parser = satisfy(|&ch: &char| ch.is_ascii_digit())
             .some()
             .map_result(|v| v.into_iter().collect::<String>().parse::<i64>());
 
@@ -305,4 +470,77 @@ mod test_functor {
             parser2.exec(&mut CharStream::new("10"))
         );
     }
+
+    #[test]
+    fn spanned_multi_line() {
+        let word = satisfy(|ch: &char| ch.is_alphabetic()).some().spanned();
+        let parser = word.clone().and(space().some().right(word));
+
+        let mut st = CharStream::new("héllo\n  wörld");
+        let (res, logs) = parser.exec(&mut st);
+
+        let (hello, world) = res.unwrap();
+        assert_eq!(0..6, hello.span.range());
+        assert_eq!(9..15, world.span.range());
+        assert_eq!((1, 2), (world.span.start.row(), world.span.start.col()));
+        assert_eq!(6, world.map(|v| v.len()).span.len());
+        assert_eq!(0, logs.len());
+    }
+
+    #[test]
+    fn spanned_tokens() {
+        use crate::core::{Pos, Span};
+
+        let span = |l, r| Span::new(Pos::from_offset(l), Pos::from_offset(r));
+        let toks = [('a', span(0, 1)), ('+', span(2, 3)), ('b', span(4, 5))];
+        let parser = satisfy_token(|ch: &char| ch.is_alphabetic())
+            .and(token('+'))
+            .spanned()
+            .and(token('b'));
+
+        let mut st = TokenStream::spanned(&toks);
+        let (res, _) = parser.exec(&mut st);
+
+        assert_eq!(span(0, 3), res.unwrap().0.span);
+    }
+
+    #[test]
+    fn spanned_empty() {
+        let parser = char('a').many().spanned();
+
+        let mut st = CharStream::new("b");
+        let (res, _) = parser.exec(&mut st);
+
+        assert!(res.unwrap().span.is_empty());
+    }
+
+    #[test]
+    fn recognize_nested() {
+        let inp = String::from("[[a], []]!");
+        let list = fix(|list| {
+            char('[')
+                .and(list.or(char('a').recognize()).sep_by(literal(", ")))
+                .and(char(']'))
+                .recognize()
+        });
+
+        let mut st = CharStream::new(&inp);
+        let (res, logs) = list.exec(&mut st);
+
+        assert_eq!(Some("[[a], []]"), res);
+        assert_eq!(inp.as_ptr(), res.unwrap().as_ptr());
+        assert_eq!(0, logs.len());
+    }
+
+    #[test]
+    fn recognize_fail_with_grace() {
+        let parser = char('a').and(char('b')).with_recognized();
+
+        let mut st = CharStream::new("ac");
+        let (res, logs) = parser.exec(&mut st);
+
+        assert_eq!(None, res);
+        assert_eq!("ac", st.as_str());
+        assert_eq!(1, logs.len());
+    }
 }
@@ -0,0 +1,270 @@
+use crate::core::{return_none, Parsable, ParseError, Parser, Stream};
+use std::fmt::Debug;
+
+/// # Combinator: `peek` (function ver.)
+///
+/// Apply given parser without consuming any input if it succeeds.
+/// If it fails, the failure is reported as is, so a failure after
+/// consuming input is still fatal to `or`.
+///
+/// # Example
+/// ```
+/// use parsic::combinators::*;
+/// use parsic::core::Parsable;
+/// use parsic::primitives::{literal, CharStream};
+///
+/// let parser = peek(literal("fn"));
+///
+/// let mut st = CharStream::new("fn main");
+/// let (res, logs) = parser.exec(&mut st);
+///
+/// assert_eq!(Some("fn"), res);
+/// assert_eq!("fn main", st.as_str());
+/// assert_eq!(0, logs.len());
+/// ```
+pub fn peek<'f, A: 'f, S: Stream>(
+    p: impl Parsable<Stream = S, Result = A> + 'f,
+) -> Parser<'f, A, S> {
+    Parser::new(move |stream: &mut S, logger| {
+        let (st, consumed) = (stream.clone(), logger.consumed);
+        let res = p.parse(stream, logger)?;
+        *stream = st;
+        logger.consumed = consumed;
+        Some(res)
+    })
+}
+
+/// # Combinator: `not_followed_by` (function ver.)
+///
+/// Succeed without consuming any input if given parser fails,
+/// and fail if it succeeds. Logs of the given parser are dropped.
+///
+/// It only checks `p`; the method `left_not_followed_by` parses the
+/// current parser first, i.e. `q.left_not_followed_by(p)` is
+/// `q.left(not_followed_by(p))`.
+///
+/// # Example
+/// ```
+/// use parsic::combinators::*;
+/// use parsic::core::Parsable;
+/// use parsic::primitives::{literal, satisfy, CharStream};
+///
+/// // Keyword `if` but not the prefix of an identifier
+/// let parser = literal("if").left(not_followed_by(satisfy(|ch: &char| ch.is_alphanumeric())));
+///
+/// let mut st1 = CharStream::new("if x");
+/// let mut st2 = CharStream::new("iffy");
+/// let (res1, logs1) = parser.exec(&mut st1);
+/// let (res2, logs2) = parser.exec(&mut st2);
+///
+/// assert_eq!(Some("if"), res1);
+/// assert_eq!(None, res2);
+/// assert_eq!((" x", "iffy"), (st1.as_str(), st2.as_str()));
+/// assert_eq!((0, 1), (logs1.len(), logs2.len()));
+/// ```
+pub fn not_followed_by<'f, A: 'f, S>(
+    p: impl Parsable<Stream = S, Result = A> + 'f,
+) -> Parser<'f, (), S>
+where
+    S: Stream,
+    S::Item: Debug,
+{
+    Parser::new(move |stream: &mut S, logger| {
+        let (st, lg) = (stream.clone(), logger.clone());
+        let res = p.parse(stream, logger);
+        *stream = st;
+        *logger = lg;
+        match res {
+            Some(_) => {
+                logger.fail(unexpected(stream));
+                None
+            }
+            None => Some(()),
+        }
+    })
+}
+
+/// # Combinator: `eof`
+///
+/// Succeed if there is no item left in the parse stream.
+///
+/// # Example
+/// ```
+/// use parsic::combinators::*;
+/// use parsic::core::Parsable;
+/// use parsic::primitives::{char, CharStream};
+///
+/// let parser = char('a').left(eof());
+///
+/// let mut st1 = CharStream::new("a");
+/// let mut st2 = CharStream::new("ab");
+/// let (res1, logs1) = parser.exec(&mut st1);
+/// let (res2, logs2) = parser.exec(&mut st2);
+///
+/// assert_eq!(Some('a'), res1);
+/// assert_eq!(None, res2);
+/// assert_eq!(
+///     "unexpected 'b', expected end of input (row: 0, col: 1)",
+///     logs2.error().unwrap().to_string()
+/// );
+/// assert_eq!((0, 1), (logs1.len(), logs2.len()));
+/// ```
+pub fn eof<'f, S>() -> Parser<'f, (), S>
+where
+    S: Stream,
+    S::Item: Debug,
+{
+    Parser::new(move |stream: &mut S, logger| match stream.peek() {
+        Some(_) => {
+            logger.fail(unexpected(stream).with_expected("end of input"));
+            None
+        }
+        None => Some(()),
+    })
+}
+
+/// Helper function that creates an error for the next item of the stream
+fn unexpected<S>(stream: &S) -> ParseError
+where
+    S: Stream,
+    S::Item: Debug,
+{
+    let next = stream
+        .peek()
+        .map_or("end of input".to_string(), |x| format!("{:?}", x));
+    ParseError::new(Some(stream.pos())).with_unexpected(&next)
+}
+
+/// Implement lookahead combinators for `Parsable<S>`.
+pub trait LookaheadExt<'f, A: 'f, S>: Parsable<Stream = S, Result = A> {
+    /// # Combinator: `peek`
+    ///
+    /// Apply current parser without consuming any input if it succeeds.
+    ///
+    /// # Example
+    /// ```
+    /// use parsic::combinators::*;
+    /// use parsic::core::Parsable;
+    /// use parsic::primitives::{char, CharStream};
+    ///
+    /// let parser = char('a').peek().and(char('a'));
+    ///
+    /// let mut st = CharStream::new("ab");
+    /// let (res, logs) = parser.exec(&mut st);
+    ///
+    /// assert_eq!(Some(('a', 'a')), res);
+    /// assert_eq!("b", st.as_str());
+    /// assert_eq!(0, logs.len());
+    /// ```
+    fn peek(self) -> Parser<'f, A, S>
+    where
+        S: Stream,
+        Self: Sized + 'f,
+    {
+        peek(self)
+    }
+
+    /// # Combinator: `left_not_followed_by`
+    ///
+    /// Apply current parser, and then succeed only if `p` fails
+    /// at the position right after it. `p` never consumes input.
+    ///
+    /// Same as `self.left(not_followed_by(p))`. Unlike the function
+    /// `not_followed_by(p)`, which only checks that `p` fails, it parses
+    /// the current parser first and returns its result.
+    ///
+    /// # Example
+    /// ```
+    /// use parsic::combinators::*;
+    /// use parsic::core::Parsable;
+    /// use parsic::primitives::{char, CharStream};
+    ///
+    /// let parser = char('<').left_not_followed_by(char('='));
+    ///
+    /// let mut st = CharStream::new("<=");
+    /// let (res, logs) = parser.exec(&mut st);
+    ///
+    /// assert_eq!(None, res);
+    /// assert_eq!("<=", st.as_str());
+    /// assert_eq!(1, logs.len());
+    /// ```
+    fn left_not_followed_by<B: 'f>(
+        self,
+        p: impl Parsable<Stream = S, Result = B> + 'f,
+    ) -> Parser<'f, A, S>
+    where
+        S: Stream + 'f,
+        S::Item: Debug,
+        Self: Sized + 'f,
+    {
+        let p = not_followed_by(p);
+        Parser::new(move |stream: &mut S, logger| {
+            let st = stream.clone();
+            let res = self.parse(stream, logger)?;
+            match p.parse(stream, logger) {
+                Some(_) => Some(res),
+                None => return_none(stream, &st, logger),
+            }
+        })
+    }
+}
+
+impl<'f, A: 'f, S, P: Parsable<Stream = S, Result = A>> LookaheadExt<'f, A, S> for P {}
+
+#[cfg(test)]
+mod test_lookahead {
+    use crate::combinators::*;
+    use crate::core::Parsable;
+    use crate::primitives::{char, literal, satisfy, CharStream};
+
+    #[test]
+    fn peek_fail_with_grace() {
+        let parser = peek(literal("ab")).or(literal("ac"));
+
+        let mut st = CharStream::new("ac");
+        let (res, logs) = parser.exec(&mut st);
+
+        // `literal` fails as a whole, so `peek` did not consume input
+        assert_eq!(Some("ac"), res);
+        assert_eq!("", st.as_str());
+        assert_eq!(0, logs.len());
+    }
+
+    #[test]
+    fn peek_keeps_commitment() {
+        let parser = peek(char('a').and(char('b'))).or(char('a').and(char('c')));
+
+        let mut st = CharStream::new("ac");
+        let (res, _) = parser.exec(&mut st);
+
+        assert_eq!(None, res);
+        assert_eq!("ac", st.as_str());
+    }
+
+    #[test]
+    fn left_not_followed_by_drops_errors() {
+        let ident = satisfy(|ch: &char| ch.is_alphanumeric());
+        let keyword = literal("if").left_not_followed_by(ident);
+        let parser = keyword.left(char(' ')).left(char('x')).left(eof());
+
+        let mut st = CharStream::new("if y");
+        let (res, logs) = parser.exec(&mut st);
+
+        assert_eq!(None, res);
+        assert_eq!(
+            "unexpected 'y', expected 'x' (row: 0, col: 3)",
+            logs.error().unwrap().to_string()
+        );
+    }
+
+    #[test]
+    fn eof_on_empty_input() {
+        let parser = char('a').many().left(eof());
+
+        let mut st = CharStream::new("");
+        let (res, logs) = parser.exec(&mut st);
+
+        assert_eq!(Some(vec![]), res);
+        assert_eq!(0, logs.len());
+    }
+}
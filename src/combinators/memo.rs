@@ -0,0 +1,179 @@
+use crate::core::{Parsable, ParseLogger, Parser, Stream};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::{Rc, Weak};
+
+/// Results of a parser keyed by the round of `fix_left_rec` and the
+/// stream index it started at: the result along with the stream after
+/// parsing, and the logger of the parse.
+type MemoTable<A, S> = HashMap<(usize, usize), (Option<(A, S)>, ParseLogger)>;
+
+/// # Combinator: `memo` (function ver.)
+///
+/// Cache the results of given parser by the position it starts at, so
+/// that it parses each position of the input at most once per parsing
+/// session (i.e. per `exec`, or per `ParseLogger` passed to `parse`).
+/// Cached results are replayed along with their logs.
+///
+/// Memoization pays off for grammars that backtrack heavily with `or`
+/// and `attempt`. Each call to `memo` creates a new table, so build the
+/// memoized parser once and clone it where it is used.
+///
+/// # Example
+/// ```
+/// use parsic::combinators::*;
+/// use parsic::core::Parsable;
+/// use parsic::primitives::{char, satisfy, CharStream};
+///
+/// // Without `memo`, `number` would be parsed twice
+/// let number = satisfy(|ch: &char| ch.is_ascii_digit()).some().memo();
+/// let parser = number.clone().left(char('+')).attempt().or(number);
+///
+/// let mut st = CharStream::new("42");
+/// let (res, logs) = parser.exec(&mut st);
+///
+/// assert_eq!(Some(vec!['4', '2']), res);
+/// assert_eq!("", st.as_str());
+/// assert_eq!(0, logs.len());
+/// ```
+pub fn memo<'f, A: Clone + 'f, S: Stream + 'f>(
+    p: impl Parsable<Stream = S, Result = A> + 'f,
+) -> Parser<'f, A, S> {
+    let table = RefCell::new((Weak::new(), MemoTable::<A, S>::new()));
+    Parser::new(move |stream: &mut S, logger| {
+        let key = (logger.round, stream.index());
+        let cached = {
+            let (session, memo) = &mut *table.borrow_mut();
+            // Drop results of previous sessions
+            if !session.ptr_eq(&Rc::downgrade(&logger.session.0)) {
+                *session = Rc::downgrade(&logger.session.0);
+                memo.clear();
+            }
+            memo.get(&key).cloned()
+        };
+        let (res, inner) = cached.unwrap_or_else(|| {
+            let mut inner = logger.nested();
+            let res = p.parse(stream, &mut inner).map(|x| (x, stream.clone()));
+            let entry = (res, inner);
+            table.borrow_mut().1.insert(key, entry.clone());
+            entry
+        });
+        logger.absorb(inner, res.is_some());
+        res.map(|(x, st)| {
+            *stream = st;
+            x
+        })
+    })
+}
+
+/// Implement `memo` combinator for `Parsable<S>`.
+pub trait MemoExt<'f, A: 'f, S>: Parsable<Stream = S, Result = A> {
+    /// # Combinator: `memo`
+    ///
+    /// Cache the results of current parser by the position it starts at,
+    /// so that it parses each position of the input at most once per
+    /// parsing session.
+    ///
+    /// # Example
+    /// ```
+    /// use parsic::combinators::*;
+    /// use parsic::core::Parsable;
+    /// use parsic::primitives::{char, CharStream};
+    ///
+    /// let a = char('a').memo();
+    /// let parser = a.clone().and(char('b')).attempt().or(a.and(char('c')));
+    ///
+    /// let mut st = CharStream::new("ac");
+    /// let (res, logs) = parser.exec(&mut st);
+    ///
+    /// assert_eq!(Some(('a', 'c')), res);
+    /// assert_eq!(0, logs.len());
+    /// ```
+    fn memo(self) -> Parser<'f, A, S>
+    where
+        A: Clone,
+        S: Stream + 'f,
+        Self: Sized + 'f,
+    {
+        memo(self)
+    }
+}
+
+impl<'f, A: 'f, S, P: Parsable<Stream = S, Result = A>> MemoExt<'f, A, S> for P {}
+
+#[cfg(test)]
+mod test_memo {
+    use crate::combinators::*;
+    use crate::core::{Parsable, Parser};
+    use crate::primitives::{char, satisfy, CharStream};
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    /// expr := term '+' expr | term '-' expr | term
+    /// term := '(' expr ')' | digit
+    fn grammar<'f>(count: Rc<Cell<usize>>, memoize: bool) -> Parser<'f, i64, CharStream<'f>> {
+        fix(move |expr| {
+            let count = count.clone();
+            let digit = satisfy(|ch: &char| ch.is_ascii_digit()).map(|ch| ch as i64 - '0' as i64);
+            let term = char('(').mid(expr.clone(), char(')')).or(digit);
+            let term = Parser::new(move |stream, logger| {
+                count.set(count.get() + 1);
+                term.parse(stream, logger)
+            });
+            let term = if memoize { term.memo() } else { term };
+
+            let plus = term.clone().left(char('+')).and(expr.clone());
+            let minus = term.clone().left(char('-')).and(expr);
+            plus.map(|(x, y)| x + y)
+                .attempt()
+                .or(minus.map(|(x, y)| x - y).attempt())
+                .or(term)
+        })
+    }
+
+    #[test]
+    fn parse_each_position_once() {
+        let inp = "((((1-2))))+3";
+        let (count1, count2) = (Rc::new(Cell::new(0)), Rc::new(Cell::new(0)));
+        let parser1 = grammar(count1.clone(), false);
+        let parser2 = grammar(count2.clone(), true);
+
+        let res1 = parser1.exec(&mut CharStream::new(inp));
+        let res2 = parser2.exec(&mut CharStream::new(inp));
+
+        assert_eq!(Some(2), res1.0);
+        assert_eq!(res1, res2);
+        assert!(count1.get() > 100);
+        // Terms start at 7 different positions
+        assert_eq!(7, count2.get());
+    }
+
+    #[test]
+    fn replay_failures() {
+        let a = char('a').memo();
+        let parser = a
+            .clone()
+            .or(char('b'))
+            .and(a)
+            .attempt()
+            .or(char('c').map(|_| ('c', 'c')));
+
+        let mut st = CharStream::new("ax");
+        let (res, logs) = parser.exec(&mut st);
+
+        assert_eq!(None, res);
+        assert_eq!("ax", st.as_str());
+        assert_eq!(
+            "unexpected 'x', expected 'a' (row: 0, col: 1)",
+            logs.error().unwrap().to_string()
+        );
+    }
+
+    #[test]
+    fn reset_between_sessions() {
+        let parser = satisfy(|ch: &char| ch.is_ascii_digit()).memo();
+
+        assert_eq!(Some('1'), parser.exec(&mut CharStream::new("1")).0);
+        assert_eq!(Some('2'), parser.exec(&mut CharStream::new("2")).0);
+    }
+}
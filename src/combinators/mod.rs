@@ -1,13 +1,16 @@
 mod alternative;
 mod applicative;
 mod error;
+mod expression;
 mod fix;
 mod functor;
+mod lookahead;
+mod memo;
 mod monad;
 mod replicative;
 mod sequential;
 
 pub use crate::combinators::{
-    self, alternative::*, applicative::*, error::*, fix::*, functor::*, monad::*, replicative::*,
-    sequential::*,
+    self, alternative::*, applicative::*, error::*, expression::*, fix::*, functor::*,
+    lookahead::*, memo::*, monad::*, replicative::*, sequential::*,
 };
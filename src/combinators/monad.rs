@@ -1,4 +1,4 @@
-use crate::core::{return_none, Parsable, Parser};
+use crate::core::{return_none, Parsable, Parser, Stream};
 
 /// # Combinator: `bind` (function ver.)
 ///
@@ -23,7 +23,7 @@ use crate::core::{return_none, Parsable, Parser};
 /// // <expr> := <uppercase_letter> '+'
 /// // <expr> := <lowercase_letter> '-'
 /// let parser = bind(
-///                 satisfy(|_| true),
+///                 satisfy(|_: &char| true),
 ///                 |ch| if ch.is_uppercase() {
 ///                     char('+')
 ///                 } else {
@@ -42,13 +42,13 @@ pub fn bind<'f, A: 'f, B: 'f, S, P>(
 ) -> Parser<'f, B, S>
 where
     P: Parsable<Stream = S, Result = B>,
-    S: Clone,
+    S: Stream,
 {
     Parser::new(move |stream: &mut S, logger| {
         let st = stream.clone();
         p.parse(stream, logger)
             .and_then(|x| f(x).parse(stream, logger))
-            .or_else(|| return_none(stream, &st))
+            .or_else(|| return_none(stream, &st, logger))
     })
 }
 
@@ -78,7 +78,7 @@ pub trait MonadExt<'f, A: 'f, S>: Parsable<Stream = S, Result = A> {
     ///
     /// // <expr> := <uppercase_letter> '+'
     /// // <expr> := <lowercase_letter> '-'
-    /// let parser = satisfy(|_| true)
+    /// let parser = satisfy(|_: &char| true)
     ///            .bind(|ch| if ch.is_uppercase() {
     ///                char('+')
     ///            } else {
@@ -93,7 +93,7 @@ pub trait MonadExt<'f, A: 'f, S>: Parsable<Stream = S, Result = A> {
     fn bind<B: 'f, P>(self, f: impl Fn(A) -> P + 'f) -> Parser<'f, B, S>
     where
         P: Parsable<Stream = S, Result = B>,
-        S: Clone,
+        S: Stream,
         Self: Sized + 'f,
     {
         bind(self, f)
@@ -110,7 +110,7 @@ mod test_monad {
 
     #[test]
     fn fail_with_grace() {
-        let parser = satisfy(|_| true).bind(|ch| {
+        let parser = satisfy(|_: &char| true).bind(|ch| {
             if ch.is_uppercase() {
                 char('+')
             } else {
@@ -143,6 +143,7 @@ mod test_monad {
     }
 
     #[test]
+    #[allow(clippy::redundant_closure)]
     fn right_identity() {
         //! `p.bind(|x| pure(x)) ~ p`
         //! Right identity law.
@@ -165,7 +166,7 @@ mod test_monad {
         //! Associative law.
         let f = |ch| if ch == '0' { char('a') } else { char('b') };
         let g = |ch| if ch == 'a' { char('A') } else { char('B') };
-        let parser1 = char('0').bind(g.clone()).bind(f.clone());
+        let parser1 = char('0').bind(f).bind(g);
         let parser2 = char('0').bind(|x| f(x).bind(g));
 
         assert_eq!(
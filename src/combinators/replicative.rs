@@ -1,10 +1,15 @@
-use crate::core::{Parsable, Parser};
+use crate::combinators::{and, map, mid, or, pure};
+use crate::core::{return_none, Parsable, ParseError, ParseLogger, Parser, Stream};
+use std::iter::FromIterator;
+use std::rc::Rc;
 
 /// # Combinator: `many` (function ver.)
 ///
 /// Apply given parser as many times as possible (**zero** or more times),
-/// and returns a vector `Vec<T>` containg all the parse results. The
-/// combinator always succeeds.
+/// and returns a vector `Vec<T>` containg all the parse results. It
+/// stops when the parser fails without consuming input, and fails when
+/// the parser fails after consuming input or passing a `cut`, or when
+/// the parser succeeds without consuming input, which would loop forever.
 ///
 /// # Example
 /// ```
@@ -25,20 +30,11 @@ use crate::core::{Parsable, Parser};
 /// assert_eq!(("ql", "ql"), (st1.as_str(), st2.as_str()));
 /// assert_eq!((0, 0), (logs1.len(), logs2.len()));
 /// ```
-pub fn many<'f, A: 'f, S: Clone>(
+pub fn many<'f, A: 'f, S: Stream>(
     p: impl Parsable<Stream = S, Result = A> + 'f,
 ) -> Parser<'f, Vec<A>, S> {
     Parser::new(move |stream: &mut S, logger| {
-        let (mut st, mut lg) = (stream.clone(), logger.clone());
-        let mut res = vec![];
-        while let Some(x) = p.parse(stream, logger) {
-            res.push(x);
-            st = stream.clone();
-            lg = logger.clone();
-        }
-        *stream = st;
-        *logger = lg;
-        Some(res)
+        replicate(&p, stream, logger, "many", 0, None, |it| it.collect()).ok()
     })
 }
 
@@ -46,7 +42,8 @@ pub fn many<'f, A: 'f, S: Clone>(
 ///
 /// Apply given parser as many times as possible (**one** or more times),
 /// and returns a vector `Vec<T>` containg all the parse results. The
-/// combinator fails if the parser fails at the first attempt.
+/// combinator fails if the parser fails at the first attempt. Like `many`,
+/// it fails if the parser succeeds without consuming input.
 ///
 /// # Example
 /// ```
@@ -67,33 +64,803 @@ pub fn many<'f, A: 'f, S: Clone>(
 /// assert_eq!(("ql", "ql"), (st1.as_str(), st2.as_str()));
 /// assert_eq!((0, 1), (logs1.len(), logs2.len()));
 /// ```
-pub fn some<'f, A: 'f, S: Clone>(
+pub fn some<'f, A: 'f, S: Stream>(
+    p: impl Parsable<Stream = S, Result = A> + 'f,
+) -> Parser<'f, Vec<A>, S> {
+    Parser::new(move |stream: &mut S, logger| {
+        replicate(&p, stream, logger, "some", 1, None, |it| it.collect()).ok()
+    })
+}
+
+/// # Combinator: `many_fold` (function ver.)
+///
+/// Apply given parser as many times as possible (**zero** or more times),
+/// and folds the parse results into an accumulator starting from `init`,
+/// without collecting them first.
+///
+/// # Example
+/// ```
+/// use parsic::combinators::*;
+/// use parsic::core::Parsable;
+/// use parsic::primitives::{satisfy, CharStream};
+///
+/// let digit = satisfy(|ch: &char| ch.is_ascii_digit()).map(|ch| ch as u64 - '0' as u64);
+/// let parser = many_fold(digit, 0, |acc, x| acc * 10 + x);
+///
+/// let mut st = CharStream::new("1024ms");
+/// let (res, logs) = parser.exec(&mut st);
+///
+/// assert_eq!(Some(1024), res);
+/// assert_eq!("ms", st.as_str());
+/// assert_eq!(0, logs.len());
+/// ```
+pub fn many_fold<'f, A: 'f, B: Clone + 'f, S: Stream>(
+    p: impl Parsable<Stream = S, Result = A> + 'f,
+    init: B,
+    f: impl Fn(B, A) -> B + 'f,
+) -> Parser<'f, B, S> {
+    Parser::new(move |stream: &mut S, logger| {
+        replicate(&p, stream, logger, "many_fold", 0, None, |it| {
+            it.fold(init.clone(), &f)
+        })
+        .ok()
+    })
+}
+
+/// # Combinator: `many_for_each` (function ver.)
+///
+/// Apply given parser as many times as possible (**zero** or more times),
+/// and calls `f` on each parse result as soon as it is parsed.
+///
+/// # Example
+/// ```
+/// use parsic::combinators::*;
+/// use parsic::core::Parsable;
+/// use parsic::primitives::{char, satisfy, CharStream};
+/// use std::cell::Cell;
+///
+/// let lines = Cell::new(0);
+/// let line = satisfy(|&ch: &char| ch != '\n').many().left(char('\n'));
+/// let parser = many_for_each(line, |_| lines.set(lines.get() + 1));
+///
+/// let mut st = CharStream::new("a\nbc\n\n");
+/// let (res, logs) = parser.exec(&mut st);
+///
+/// assert_eq!(Some(()), res);
+/// assert_eq!(3, lines.get());
+/// assert_eq!(0, logs.len());
+/// ```
+pub fn many_for_each<'f, A: 'f, S: Stream>(
+    p: impl Parsable<Stream = S, Result = A> + 'f,
+    f: impl Fn(A) + 'f,
+) -> Parser<'f, (), S> {
+    Parser::new(move |stream: &mut S, logger| {
+        replicate(&p, stream, logger, "many_for_each", 0, None, |it| {
+            it.for_each(&f)
+        })
+        .ok()
+    })
+}
+
+/// # Combinator: `many_collect` (function ver.)
+///
+/// Apply given parser as many times as possible (**zero** or more times),
+/// and collects the parse results into any collection `C` that
+/// implements `FromIterator`, e.g. `String` from `char`s.
+///
+/// # Example
+/// ```
+/// use parsic::combinators::*;
+/// use parsic::core::Parsable;
+/// use parsic::primitives::{satisfy, CharStream};
+///
+/// let parser = many_collect::<String, _, _>(satisfy(|ch: &char| ch.is_alphabetic()));
+///
+/// let mut st = CharStream::new("abc123");
+/// let (res, logs) = parser.exec(&mut st);
+///
+/// assert_eq!(Some("abc".to_string()), res);
+/// assert_eq!("123", st.as_str());
+/// assert_eq!(0, logs.len());
+/// ```
+pub fn many_collect<'f, C: FromIterator<A> + 'f, A: 'f, S: Stream>(
+    p: impl Parsable<Stream = S, Result = A> + 'f,
+) -> Parser<'f, C, S> {
+    Parser::new(move |stream: &mut S, logger| {
+        replicate(&p, stream, logger, "many_collect", 0, None, |it| {
+            C::from_iter(it)
+        })
+        .ok()
+    })
+}
+
+/// # Combinator: `count` (function ver.)
+///
+/// Apply given parser exactly `n` times, and returns a vector `Vec<T>`
+/// containing all the parse results.
+///
+/// # Example
+/// ```
+/// use parsic::combinators::*;
+/// use parsic::core::Parsable;
+/// use parsic::primitives::{satisfy, CharStream};
+///
+/// let hex = satisfy(|ch: &char| ch.is_ascii_hexdigit());
+/// let parser = count(hex, 4);
+///
+/// let mut st1 = CharStream::new("00ff7");
+/// let mut st2 = CharStream::new("0fx");
+/// let (res1, logs1) = parser.exec(&mut st1);
+/// let (res2, logs2) = parser.exec(&mut st2);
+///
+/// assert_eq!(Some(vec!['0', '0', 'f', 'f']), res1);
+/// assert_eq!(None, res2);
+/// assert_eq!(("7", "0fx"), (st1.as_str(), st2.as_str()));
+/// assert_eq!((0, 1), (logs1.len(), logs2.len()));
+/// ```
+pub fn count<'f, A: 'f, S: Stream>(
+    p: impl Parsable<Stream = S, Result = A> + 'f,
+    n: usize,
+) -> Parser<'f, Vec<A>, S> {
+    many_m_n(p, n, n)
+}
+
+/// # Combinator: `at_least` (function ver.)
+///
+/// Apply given parser as many times as possible (`n` or more times),
+/// and returns a vector `Vec<T>` containing all the parse results.
+pub fn at_least<'f, A: 'f, S: Stream>(
+    p: impl Parsable<Stream = S, Result = A> + 'f,
+    n: usize,
+) -> Parser<'f, Vec<A>, S> {
+    Parser::new(move |stream: &mut S, logger| bounded(&p, stream, logger, "at_least", n, None))
+}
+
+/// # Combinator: `at_most` (function ver.)
+///
+/// Apply given parser as many times as possible, but at most `n` times,
+/// and returns a vector `Vec<T>` containing all the parse results. It
+/// fails only when the parser fails after consuming input or passing
+/// a `cut`.
+pub fn at_most<'f, A: 'f, S: Stream>(
+    p: impl Parsable<Stream = S, Result = A> + 'f,
+    n: usize,
+) -> Parser<'f, Vec<A>, S> {
+    many_m_n(p, 0, n)
+}
+
+/// # Combinator: `many_m_n` (function ver.)
+///
+/// Apply given parser as many times as possible, at least `min` and at
+/// most `max` times, and returns a vector `Vec<T>` containing all the
+/// parse results. If fewer than `min` results are found, the parser
+/// fails with an error telling how many were found.
+///
+/// # Panics
+/// Panics if `min` is greater than `max`.
+///
+/// # Example
+/// ```
+/// use parsic::combinators::*;
+/// use parsic::core::Parsable;
+/// use parsic::primitives::{char, CharStream};
+///
+/// let parser = many_m_n(char('a'), 2, 3);
+///
+/// let mut st1 = CharStream::new("aaaa");
+/// let mut st2 = CharStream::new("ab");
+/// let (res1, logs1) = parser.exec(&mut st1);
+/// let (res2, logs2) = parser.exec(&mut st2);
+///
+/// assert_eq!(Some(vec!['a', 'a', 'a']), res1);
+/// assert_eq!(None, res2);
+/// assert_eq!(("a", "ab"), (st1.as_str(), st2.as_str()));
+/// assert_eq!(1, logs2.len());
+/// assert_eq!(
+///     "unexpected 1 item, expected at least 2 items (row: 0, col: 0)",
+///     logs2.error().unwrap().to_string()
+/// );
+/// ```
+pub fn many_m_n<'f, A: 'f, S: Stream>(
+    p: impl Parsable<Stream = S, Result = A> + 'f,
+    min: usize,
+    max: usize,
+) -> Parser<'f, Vec<A>, S> {
+    assert!(min <= max, "`many_m_n` requires `min <= max`");
+    Parser::new(move |stream: &mut S, logger| {
+        bounded(&p, stream, logger, "many_m_n", min, Some(max))
+    })
+}
+
+/// # Combinator: `many_till` (function ver.)
+///
+/// Apply given parser zero or more times until `end` succeeds, and
+/// returns a vector `Vec<T>` containing the results of `p`. The result
+/// of `end` is dropped. Like `many`, it fails if `p` succeeds without
+/// consuming input.
+///
+/// # Example
+/// ```
+/// use parsic::combinators::*;
+/// use parsic::core::Parsable;
+/// use parsic::primitives::{literal, satisfy, CharStream};
+///
+/// let parser = literal("<!--").right(many_till(satisfy(|_: &char| true), literal("-->")));
+///
+/// let mut st = CharStream::new("<!-- a -> b -->c");
+/// let (res, logs) = parser.exec(&mut st);
+///
+/// assert_eq!(Some(" a -> b ".to_string()), res.map(|v| v.into_iter().collect()));
+/// assert_eq!("c", st.as_str());
+/// assert_eq!(0, logs.len());
+/// ```
+pub fn many_till<'f, A: 'f, B: 'f, S: Stream>(
     p: impl Parsable<Stream = S, Result = A> + 'f,
+    end: impl Parsable<Stream = S, Result = B> + 'f,
 ) -> Parser<'f, Vec<A>, S> {
     Parser::new(move |stream: &mut S, logger| {
-        let (mut st, mut lg) = (stream.clone(), logger.clone());
+        let st = stream.clone();
         let mut res = vec![];
-        while let Some(x) = p.parse(stream, logger) {
-            res.push(x);
-            st = stream.clone();
-            lg = logger.clone();
+        loop {
+            let bak = stream.clone();
+            match try_parse(&end, stream, logger) {
+                Some(Some(_)) => return Some(res),
+                Some(None) => (),
+                None => return return_none(stream, &st, logger),
+            }
+            match p.parse(stream, logger) {
+                Some(_) if !progress("many_till", &bak, stream, logger) => {
+                    return return_none(stream, &st, logger)
+                }
+                Some(x) => res.push(x),
+                None => return return_none(stream, &st, logger),
+            }
         }
-        *stream = st;
-        if res.is_empty() {
+    })
+}
+
+/// Helper function that applies given parser at least `min` and at most
+/// `max` times, and fails with how many results were found if not enough.
+fn bounded<A, S: Stream>(
+    p: &impl Parsable<Stream = S, Result = A>,
+    stream: &mut S,
+    logger: &mut ParseLogger,
+    name: &str,
+    min: usize,
+    max: Option<usize>,
+) -> Option<Vec<A>> {
+    let pos = stream.pos();
+    match replicate(p, stream, logger, name, min, max, |it| it.collect()) {
+        Ok(res) => Some(res),
+        Err(n) => {
+            if n < min {
+                let items = |n| format!("{} item{}", n, if n == 1 { "" } else { "s" });
+                let expected = match max == Some(min) {
+                    true => items(min),
+                    false => format!("at least {}", items(min)),
+                };
+                let err = ParseError::new(Some(pos))
+                    .with_unexpected(&items(n))
+                    .with_expected(&expected);
+                // Report the count in place of where the last item stopped
+                logger.error = None;
+                logger.fail(err);
+            }
             None
-        } else {
-            *logger = lg;
-            Some(res)
         }
+    }
+}
+
+/// Helper function that applies given parser as many times as possible
+/// but at most `max` times, and hands the results over to `f` as an
+/// iterator. Fails with the number of results found if the parser
+/// succeeds less than `min` times.
+fn replicate<A, B, S, P>(
+    p: &P,
+    stream: &mut S,
+    logger: &mut ParseLogger,
+    name: &str,
+    min: usize,
+    max: Option<usize>,
+    f: impl FnOnce(&mut Replicate<P, S>) -> B,
+) -> Result<B, usize>
+where
+    S: Stream,
+    P: Parsable<Stream = S, Result = A>,
+{
+    let mut iter = Replicate {
+        start: stream.clone(),
+        outer: (logger.consumed, logger.cut),
+        p,
+        stream,
+        logger,
+        name,
+        min,
+        max,
+        count: 0,
+        done: None,
+    };
+    let res = f(&mut iter);
+    iter.finish().map(|_| res)
+}
+
+/// Iterator over the results of applying a parser repeatedly, see
+/// `replicate`. Each application is an alternative on its own: a
+/// failure after consuming input is fatal.
+///
+/// Without `max`, a parser that succeeds without consuming input would
+/// be applied forever, so this is reported as a fatal error of the
+/// combinator `name` instead.
+struct Replicate<'a, P, S> {
+    p: &'a P,
+    stream: &'a mut S,
+    logger: &'a mut ParseLogger,
+    name: &'a str,
+    min: usize,
+    max: Option<usize>,
+    start: S,
+    outer: (bool, bool),
+    count: usize,
+    /// Whether the repetition has stopped successfully, if stopped
+    done: Option<bool>,
+}
+
+impl<'a, P, S: Stream> Replicate<'a, P, S> {
+    /// Stop the repetition with a failure
+    fn fail(&mut self) {
+        if self
+            .logger
+            .has_consumed(self.start.index(), self.stream.index())
+        {
+            self.logger.consume();
+        }
+        self.logger.leave(self.outer, false);
+        *self.stream = self.start.clone();
+        self.done = Some(false);
+    }
+
+    /// Return the number of results, or the number of results found
+    /// before the failure
+    fn finish(self) -> Result<usize, usize> {
+        match self.done {
+            Some(false) => Err(self.count),
+            _ => {
+                self.logger.leave(self.outer, true);
+                Ok(self.count)
+            }
+        }
+    }
+}
+
+impl<'a, A, P, S> Iterator for Replicate<'a, P, S>
+where
+    S: Stream,
+    P: Parsable<Stream = S, Result = A>,
+{
+    type Item = A;
+
+    fn next(&mut self) -> Option<A> {
+        if self.done.is_some() || matches!(self.max, Some(max) if self.count >= max) {
+            return None;
+        }
+        let (bak, lg) = (self.stream.clone(), self.logger.clone());
+        let inner = self.logger.enter();
+        match self.p.parse(self.stream, self.logger) {
+            Some(_)
+                if self.max.is_none() && !progress(self.name, &bak, self.stream, self.logger) =>
+            {
+                self.fail();
+                None
+            }
+            Some(x) => {
+                self.count += 1;
+                self.logger.leave(inner, true);
+                Some(x)
+            }
+            None if self.logger.is_committed()
+                || self.logger.has_consumed(bak.index(), self.stream.index())
+                || self.count < self.min =>
+            {
+                self.fail();
+                None
+            }
+            None => {
+                *self.stream = bak;
+                self.logger.restore(lg);
+                self.done = Some(true);
+                None
+            }
+        }
+    }
+}
+
+/// Helper function that checks if an iteration of the repetition `name`
+/// has consumed input since `bak`. Otherwise the repetition would go on
+/// forever, so a fatal error is logged at the start of the iteration.
+fn progress<S: Stream>(name: &str, bak: &S, stream: &S, logger: &mut ParseLogger) -> bool {
+    if stream.index() != bak.index() {
+        return true;
+    }
+    logger.fatal(
+        ParseError::new(Some(bak.pos()))
+            .with_unexpected(&format!("empty match repeated by `{}`", name))
+            .with_expected("parser that consumes input"),
+    );
+    false
+}
+
+/// # Enum `TrailingPolicy`
+/// Whether a separator is allowed after the last item of a list.
+///
+/// - `Forbidden`: `a, b`
+/// - `Allowed`: `a, b` or `a, b,`
+/// - `Required`: `a, b,`
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TrailingPolicy {
+    Forbidden,
+    Allowed,
+    Required,
+}
+
+/// # Combinator: `sep_by` (function ver.)
+///
+/// Parse **zero** or more `p` separated by `sep`, and returns
+/// a vector `Vec<T>` containing the results of `p`.
+///
+/// # Example
+/// ```
+/// use parsic::combinators::*;
+/// use parsic::core::Parsable;
+/// use parsic::primitives::{char, satisfy, CharStream};
+///
+/// let parser = sep_by(satisfy(|ch: &char| ch.is_ascii_digit()), char(','));
+///
+/// let mut st = CharStream::new("1,2,3;");
+/// let (res, logs) = parser.exec(&mut st);
+///
+/// assert_eq!(Some(vec!['1', '2', '3']), res);
+/// assert_eq!(";", st.as_str());
+/// assert_eq!(0, logs.len());
+/// ```
+pub fn sep_by<'f, A: 'f, B: 'f, S: Stream>(
+    p: impl Parsable<Stream = S, Result = A> + 'f,
+    sep: impl Parsable<Stream = S, Result = B> + 'f,
+) -> Parser<'f, Vec<A>, S> {
+    Parser::new(move |stream: &mut S, logger| {
+        list(
+            &p,
+            &sep,
+            stream,
+            logger,
+            "sep_by",
+            0,
+            TrailingPolicy::Forbidden,
+        )
+    })
+}
+
+/// # Combinator: `sep_by1` (function ver.)
+///
+/// Parse **one** or more `p` separated by `sep`, and returns
+/// a vector `Vec<T>` containing the results of `p`.
+///
+/// # Example
+/// ```
+/// use parsic::combinators::*;
+/// use parsic::core::Parsable;
+/// use parsic::primitives::{char, satisfy, CharStream};
+///
+/// let parser = sep_by1(satisfy(|ch: &char| ch.is_ascii_digit()), char(','));
+///
+/// let mut st = CharStream::new(";");
+/// let (res, logs) = parser.exec(&mut st);
+///
+/// assert_eq!(None, res);
+/// assert_eq!(1, logs.len());
+/// ```
+pub fn sep_by1<'f, A: 'f, B: 'f, S: Stream>(
+    p: impl Parsable<Stream = S, Result = A> + 'f,
+    sep: impl Parsable<Stream = S, Result = B> + 'f,
+) -> Parser<'f, Vec<A>, S> {
+    Parser::new(move |stream: &mut S, logger| {
+        list(
+            &p,
+            &sep,
+            stream,
+            logger,
+            "sep_by1",
+            1,
+            TrailingPolicy::Forbidden,
+        )
+    })
+}
+
+/// # Combinator: `end_by` (function ver.)
+///
+/// Parse **zero** or more `p`, each followed by `sep`, and returns
+/// a vector `Vec<T>` containing the results of `p`.
+///
+/// # Example
+/// ```
+/// use parsic::combinators::*;
+/// use parsic::core::Parsable;
+/// use parsic::primitives::{char, satisfy, CharStream};
+///
+/// let parser = end_by(satisfy(|ch: &char| ch.is_ascii_digit()), char(';'));
+///
+/// let mut st = CharStream::new("1;2;");
+/// let (res, logs) = parser.exec(&mut st);
+///
+/// assert_eq!(Some(vec!['1', '2']), res);
+/// assert_eq!("", st.as_str());
+/// assert_eq!(0, logs.len());
+/// ```
+pub fn end_by<'f, A: 'f, B: 'f, S: Stream>(
+    p: impl Parsable<Stream = S, Result = A> + 'f,
+    sep: impl Parsable<Stream = S, Result = B> + 'f,
+) -> Parser<'f, Vec<A>, S> {
+    Parser::new(move |stream: &mut S, logger| {
+        list(
+            &p,
+            &sep,
+            stream,
+            logger,
+            "end_by",
+            0,
+            TrailingPolicy::Required,
+        )
+    })
+}
+
+/// # Combinator: `sep_end_by` (function ver.)
+///
+/// Parse **zero** or more `p` separated by `sep`, optionally followed
+/// by a trailing `sep`, and returns a vector `Vec<T>` containing the
+/// results of `p`.
+///
+/// # Example
+/// ```
+/// use parsic::combinators::*;
+/// use parsic::core::Parsable;
+/// use parsic::primitives::{char, satisfy, CharStream};
+///
+/// let parser = sep_end_by(satisfy(|ch: &char| ch.is_ascii_digit()), char(','));
+///
+/// let mut st = CharStream::new("1,2,");
+/// let (res, logs) = parser.exec(&mut st);
+///
+/// assert_eq!(Some(vec!['1', '2']), res);
+/// assert_eq!("", st.as_str());
+/// assert_eq!(0, logs.len());
+/// ```
+pub fn sep_end_by<'f, A: 'f, B: 'f, S: Stream>(
+    p: impl Parsable<Stream = S, Result = A> + 'f,
+    sep: impl Parsable<Stream = S, Result = B> + 'f,
+) -> Parser<'f, Vec<A>, S> {
+    Parser::new(move |stream: &mut S, logger| {
+        list(
+            &p,
+            &sep,
+            stream,
+            logger,
+            "sep_end_by",
+            0,
+            TrailingPolicy::Allowed,
+        )
+    })
+}
+
+/// # Combinator: `delimited_list` (function ver.)
+///
+/// Parse a list of **zero** or more `item` separated by `sep` between
+/// `open` and `close`, with a trailing `sep` as given by `policy`.
+///
+/// # Example
+/// ```
+/// use parsic::combinators::*;
+/// use parsic::core::Parsable;
+/// use parsic::primitives::{char, satisfy, CharStream};
+///
+/// let digit = satisfy(|ch: &char| ch.is_ascii_digit());
+/// let parser = delimited_list(char('['), digit, char(','), char(']'), TrailingPolicy::Allowed);
+///
+/// let mut st = CharStream::new("[1,2,]");
+/// let (res, logs) = parser.exec(&mut st);
+///
+/// assert_eq!(Some(vec!['1', '2']), res);
+/// assert_eq!("", st.as_str());
+/// assert_eq!(0, logs.len());
+/// ```
+pub fn delimited_list<'f, A: 'f, B: 'f, L: 'f, R: 'f, S: Stream + 'f>(
+    open: impl Parsable<Stream = S, Result = L> + 'f,
+    item: impl Parsable<Stream = S, Result = A> + 'f,
+    sep: impl Parsable<Stream = S, Result = B> + 'f,
+    close: impl Parsable<Stream = S, Result = R> + 'f,
+    policy: TrailingPolicy,
+) -> Parser<'f, Vec<A>, S> {
+    let items = Parser::new(move |stream: &mut S, logger| {
+        list(&item, &sep, stream, logger, "delimited_list", 0, policy)
+    });
+    mid(open, items, close)
+}
+
+/// Helper function that parses `p` without failing if it fails
+/// without consuming input, in which case `Some(None)` is returned.
+fn try_parse<A, S: Stream>(
+    p: &impl Parsable<Stream = S, Result = A>,
+    stream: &mut S,
+    logger: &mut ParseLogger,
+) -> Option<Option<A>> {
+    let (bak, lg) = (stream.clone(), logger.clone());
+    let outer = logger.enter();
+    let res = p.parse(stream, logger);
+    let consumed = logger.has_consumed(bak.index(), stream.index());
+    if res.is_none() && !logger.is_committed() && !consumed {
+        *stream = bak;
+        logger.restore(lg);
+        return Some(None);
+    }
+    logger.leave(outer, res.is_some());
+    res.map(Some)
+}
+
+/// Helper function that parses a list of at least `min` `p`
+/// separated by `sep`, with a trailing `sep` as given by `policy`.
+/// Fails if an item and a separator succeed without consuming input.
+fn list<A, B, S: Stream>(
+    p: &impl Parsable<Stream = S, Result = A>,
+    sep: &impl Parsable<Stream = S, Result = B>,
+    stream: &mut S,
+    logger: &mut ParseLogger,
+    name: &str,
+    min: usize,
+    policy: TrailingPolicy,
+) -> Option<Vec<A>> {
+    let st = stream.clone();
+    let mut res = vec![];
+    loop {
+        let bak = stream.clone();
+        // An item is mandatory until `min` is reached, and after a
+        // separator unless a trailing separator is allowed
+        let required = res.len() < min || (!res.is_empty() && policy == TrailingPolicy::Forbidden);
+        let item = match required {
+            true => p.parse(stream, logger).map(Some),
+            false => try_parse(p, stream, logger),
+        };
+        match item {
+            Some(Some(x)) => res.push(x),
+            Some(None) => break,
+            None => return return_none(stream, &st, logger),
+        }
+
+        let sep = match policy {
+            TrailingPolicy::Required => sep.parse(stream, logger).map(Some),
+            _ => try_parse(sep, stream, logger),
+        };
+        match sep {
+            Some(Some(_)) if !progress(name, &bak, stream, logger) => {
+                return return_none(stream, &st, logger)
+            }
+            Some(Some(_)) => (),
+            Some(None) => break,
+            None => return return_none(stream, &st, logger),
+        }
+    }
+    Some(res)
+}
+
+/// # Combinator: `chainl1` (function ver.)
+///
+/// Parse **one** or more `p` separated by `op`, and combine the results
+/// of `p` with the functions returned by `op`, associating to the left.
+///
+/// # Example
+/// ```
+/// use parsic::combinators::*;
+/// use parsic::core::Parsable;
+/// use parsic::primitives::{char, satisfy, CharStream};
+///
+/// let num = satisfy(|ch: &char| ch.is_ascii_digit()).map(|ch| ch as i64 - '0' as i64);
+/// let sub = char('-').map(|_| |x, y| x - y);
+/// let parser = chainl1(num, sub);
+///
+/// let mut st = CharStream::new("9-3-2");
+/// let (res, logs) = parser.exec(&mut st);
+///
+/// assert_eq!(Some(4), res);
+/// assert_eq!("", st.as_str());
+/// assert_eq!(0, logs.len());
+/// ```
+pub fn chainl1<'f, A: 'f, F, S: Stream + 'f>(
+    p: impl Parsable<Stream = S, Result = A> + 'f,
+    op: impl Parsable<Stream = S, Result = F> + 'f,
+) -> Parser<'f, A, S>
+where
+    F: Fn(A, A) -> A + 'f,
+{
+    let p = Rc::new(p);
+    map(and(p.clone(), many(and(op, p))), |(x, v)| {
+        v.into_iter().fold(x, |acc, (f, y)| f(acc, y))
+    })
+}
+
+/// # Combinator: `chainr1` (function ver.)
+///
+/// Parse **one** or more `p` separated by `op`, and combine the results
+/// of `p` with the functions returned by `op`, associating to the right.
+///
+/// # Example
+/// ```
+/// use parsic::combinators::*;
+/// use parsic::core::Parsable;
+/// use parsic::primitives::{char, satisfy, CharStream};
+///
+/// let num = satisfy(|ch: &char| ch.is_ascii_digit()).map(|ch| ch as u32 - '0' as u32);
+/// let pow = char('^').map(|_| |x: u32, y| x.pow(y));
+/// let parser = chainr1(num, pow);
+///
+/// let mut st = CharStream::new("2^3^2");
+/// let (res, logs) = parser.exec(&mut st);
+///
+/// assert_eq!(Some(512), res);
+/// assert_eq!("", st.as_str());
+/// assert_eq!(0, logs.len());
+/// ```
+pub fn chainr1<'f, A: 'f, F, S: Stream + 'f>(
+    p: impl Parsable<Stream = S, Result = A> + 'f,
+    op: impl Parsable<Stream = S, Result = F> + 'f,
+) -> Parser<'f, A, S>
+where
+    F: Fn(A, A) -> A + 'f,
+{
+    let p = Rc::new(p);
+    map(and(p.clone(), many(and(op, p))), |(x, v)| {
+        // Shift operands to the left so that each operator
+        // is paired with its left operand, then fold from the right
+        let (mut lhs, mut pairs) = (x, Vec::with_capacity(v.len()));
+        for (f, y) in v {
+            pairs.push((lhs, f));
+            lhs = y;
+        }
+        pairs.into_iter().rev().fold(lhs, |acc, (x, f)| f(x, acc))
     })
 }
 
+/// # Combinator: `chainl` (function ver.)
+///
+/// Same as `chainl1`, but returns `x` if there is no `p` at all.
+pub fn chainl<'f, A: Clone + 'f, F, S: Stream + 'f>(
+    p: impl Parsable<Stream = S, Result = A> + 'f,
+    op: impl Parsable<Stream = S, Result = F> + 'f,
+    x: A,
+) -> Parser<'f, A, S>
+where
+    F: Fn(A, A) -> A + 'f,
+{
+    or(chainl1(p, op), pure(x))
+}
+
+/// # Combinator: `chainr` (function ver.)
+///
+/// Same as `chainr1`, but returns `x` if there is no `p` at all.
+pub fn chainr<'f, A: Clone + 'f, F, S: Stream + 'f>(
+    p: impl Parsable<Stream = S, Result = A> + 'f,
+    op: impl Parsable<Stream = S, Result = F> + 'f,
+    x: A,
+) -> Parser<'f, A, S>
+where
+    F: Fn(A, A) -> A + 'f,
+{
+    or(chainr1(p, op), pure(x))
+}
+
 pub trait ReplicativeExt<'f, A: 'f, S>: Parsable<Stream = S, Result = A> {
     /// # Combinator: `many`
     ///
     /// Apply given parser as many times as possible (zero or more times),
-    /// and returns a vector `Vec<T>` containg all the parse results. The
-    /// combinator always succeeds.
+    /// and returns a vector `Vec<T>` containg all the parse results. It
+    /// fails when the parser fails after consuming input or passing a
+    /// `cut`, or succeeds without consuming input.
     ///
     /// # Example
     /// ```
@@ -116,7 +883,7 @@ pub trait ReplicativeExt<'f, A: 'f, S>: Parsable<Stream = S, Result = A> {
     /// ```
     fn many(self) -> Parser<'f, Vec<A>, S>
     where
-        S: Clone,
+        S: Stream,
         Self: Sized + 'f,
     {
         many(self)
@@ -149,11 +916,667 @@ pub trait ReplicativeExt<'f, A: 'f, S>: Parsable<Stream = S, Result = A> {
     /// ```
     fn some(self) -> Parser<'f, Vec<A>, S>
     where
-        S: Clone,
+        S: Stream,
         Self: Sized + 'f,
     {
         some(self)
     }
+
+    /// # Combinator: `many_fold`
+    ///
+    /// Apply current parser as many times as possible (**zero** or more
+    /// times), and folds the parse results starting from `init`.
+    ///
+    /// # Example
+    /// ```
+    /// use parsic::combinators::*;
+    /// use parsic::core::Parsable;
+    /// use parsic::primitives::{char, CharStream};
+    ///
+    /// let parser = char('+').many_fold(0, |n, _| n + 1);
+    ///
+    /// let mut st = CharStream::new("+++-");
+    /// let (res, logs) = parser.exec(&mut st);
+    ///
+    /// assert_eq!(Some(3), res);
+    /// assert_eq!("-", st.as_str());
+    /// assert_eq!(0, logs.len());
+    /// ```
+    fn many_fold<B: Clone + 'f>(self, init: B, f: impl Fn(B, A) -> B + 'f) -> Parser<'f, B, S>
+    where
+        S: Stream,
+        Self: Sized + 'f,
+    {
+        many_fold(self, init, f)
+    }
+
+    /// # Combinator: `many_for_each`
+    ///
+    /// Apply current parser as many times as possible (**zero** or more
+    /// times), and calls `f` on each parse result.
+    fn many_for_each(self, f: impl Fn(A) + 'f) -> Parser<'f, (), S>
+    where
+        S: Stream,
+        Self: Sized + 'f,
+    {
+        many_for_each(self, f)
+    }
+
+    /// # Combinator: `many_collect`
+    ///
+    /// Apply current parser as many times as possible (**zero** or more
+    /// times), and collects the parse results into a collection `C`.
+    ///
+    /// # Example
+    /// ```
+    /// use parsic::combinators::*;
+    /// use parsic::core::Parsable;
+    /// use parsic::primitives::{satisfy, CharStream};
+    ///
+    /// let parser = satisfy(|ch: &char| ch.is_ascii_digit()).many_collect::<String>();
+    ///
+    /// let mut st = CharStream::new("42!");
+    /// let (res, logs) = parser.exec(&mut st);
+    ///
+    /// assert_eq!(Some("42".to_string()), res);
+    /// assert_eq!("!", st.as_str());
+    /// assert_eq!(0, logs.len());
+    /// ```
+    fn many_collect<C: FromIterator<A> + 'f>(self) -> Parser<'f, C, S>
+    where
+        S: Stream,
+        Self: Sized + 'f,
+    {
+        many_collect(self)
+    }
+
+    /// # Combinator: `count`
+    ///
+    /// Apply current parser exactly `n` times.
+    ///
+    /// # Example
+    /// ```
+    /// use parsic::combinators::*;
+    /// use parsic::core::Parsable;
+    /// use parsic::primitives::{satisfy, CharStream};
+    ///
+    /// let parser = satisfy(|ch: &char| ch.is_ascii_hexdigit()).count(2);
+    ///
+    /// let mut st = CharStream::new("7f0");
+    /// let (res, logs) = parser.exec(&mut st);
+    ///
+    /// assert_eq!(Some(vec!['7', 'f']), res);
+    /// assert_eq!("0", st.as_str());
+    /// assert_eq!(0, logs.len());
+    /// ```
+    fn count(self, n: usize) -> Parser<'f, Vec<A>, S>
+    where
+        S: Stream,
+        Self: Sized + 'f,
+    {
+        count(self, n)
+    }
+
+    /// # Combinator: `at_least`
+    ///
+    /// Apply current parser as many times as possible (`n` or more times).
+    fn at_least(self, n: usize) -> Parser<'f, Vec<A>, S>
+    where
+        S: Stream,
+        Self: Sized + 'f,
+    {
+        at_least(self, n)
+    }
+
+    /// # Combinator: `at_most`
+    ///
+    /// Apply current parser as many times as possible, but at most `n` times.
+    fn at_most(self, n: usize) -> Parser<'f, Vec<A>, S>
+    where
+        S: Stream,
+        Self: Sized + 'f,
+    {
+        at_most(self, n)
+    }
+
+    /// # Combinator: `many_m_n`
+    ///
+    /// Apply current parser as many times as possible, at least `min`
+    /// and at most `max` times.
+    ///
+    /// # Panics
+    /// Panics if `min` is greater than `max`.
+    fn many_m_n(self, min: usize, max: usize) -> Parser<'f, Vec<A>, S>
+    where
+        S: Stream,
+        Self: Sized + 'f,
+    {
+        many_m_n(self, min, max)
+    }
+
+    /// # Combinator: `many_till`
+    ///
+    /// Apply current parser zero or more times until `end` succeeds.
+    ///
+    /// # Example
+    /// ```
+    /// use parsic::combinators::*;
+    /// use parsic::core::Parsable;
+    /// use parsic::primitives::{char, satisfy, CharStream};
+    ///
+    /// let parser = satisfy(|_: &char| true).many_till(char(';'));
+    ///
+    /// let mut st = CharStream::new("ab;c");
+    /// let (res, logs) = parser.exec(&mut st);
+    ///
+    /// assert_eq!(Some(vec!['a', 'b']), res);
+    /// assert_eq!("c", st.as_str());
+    /// assert_eq!(0, logs.len());
+    /// ```
+    fn many_till<B: 'f>(
+        self,
+        end: impl Parsable<Stream = S, Result = B> + 'f,
+    ) -> Parser<'f, Vec<A>, S>
+    where
+        S: Stream,
+        Self: Sized + 'f,
+    {
+        many_till(self, end)
+    }
+
+    /// # Combinator: `sep_by`
+    ///
+    /// Parse **zero** or more current parser separated by `sep`.
+    ///
+    /// # Example
+    /// ```
+    /// use parsic::combinators::*;
+    /// use parsic::core::Parsable;
+    /// use parsic::primitives::{char, satisfy, CharStream};
+    ///
+    /// let parser = satisfy(|ch: &char| ch.is_ascii_digit()).sep_by(char(','));
+    ///
+    /// let mut st = CharStream::new("1,2,3");
+    /// let (res, logs) = parser.exec(&mut st);
+    ///
+    /// assert_eq!(Some(vec!['1', '2', '3']), res);
+    /// assert_eq!(0, logs.len());
+    /// ```
+    fn sep_by<B: 'f>(self, sep: impl Parsable<Stream = S, Result = B> + 'f) -> Parser<'f, Vec<A>, S>
+    where
+        S: Stream,
+        Self: Sized + 'f,
+    {
+        sep_by(self, sep)
+    }
+
+    /// # Combinator: `sep_by1`
+    ///
+    /// Parse **one** or more current parser separated by `sep`.
+    fn sep_by1<B: 'f>(
+        self,
+        sep: impl Parsable<Stream = S, Result = B> + 'f,
+    ) -> Parser<'f, Vec<A>, S>
+    where
+        S: Stream,
+        Self: Sized + 'f,
+    {
+        sep_by1(self, sep)
+    }
+
+    /// # Combinator: `end_by`
+    ///
+    /// Parse **zero** or more current parser, each followed by `sep`.
+    fn end_by<B: 'f>(self, sep: impl Parsable<Stream = S, Result = B> + 'f) -> Parser<'f, Vec<A>, S>
+    where
+        S: Stream,
+        Self: Sized + 'f,
+    {
+        end_by(self, sep)
+    }
+
+    /// # Combinator: `sep_end_by`
+    ///
+    /// Parse **zero** or more current parser separated by `sep`,
+    /// optionally followed by a trailing `sep`.
+    fn sep_end_by<B: 'f>(
+        self,
+        sep: impl Parsable<Stream = S, Result = B> + 'f,
+    ) -> Parser<'f, Vec<A>, S>
+    where
+        S: Stream,
+        Self: Sized + 'f,
+    {
+        sep_end_by(self, sep)
+    }
+
+    /// # Combinator: `delimited_list`
+    ///
+    /// Parse a list of **zero** or more current parser separated by `sep`
+    /// between `open` and `close`, with a trailing `sep` as given by `policy`.
+    ///
+    /// # Example
+    /// ```
+    /// use parsic::combinators::*;
+    /// use parsic::core::Parsable;
+    /// use parsic::primitives::{char, satisfy, CharStream};
+    ///
+    /// let parser = satisfy(|ch: &char| ch.is_ascii_digit())
+    ///     .delimited_list(char('('), char(','), char(')'), TrailingPolicy::Forbidden);
+    ///
+    /// let mut st = CharStream::new("(1,2,)");
+    /// let (res, logs) = parser.exec(&mut st);
+    ///
+    /// assert_eq!(None, res);
+    /// assert_eq!("(1,2,)", st.as_str());
+    /// assert_eq!(1, logs.len());
+    /// ```
+    fn delimited_list<B: 'f, L: 'f, R: 'f>(
+        self,
+        open: impl Parsable<Stream = S, Result = L> + 'f,
+        sep: impl Parsable<Stream = S, Result = B> + 'f,
+        close: impl Parsable<Stream = S, Result = R> + 'f,
+        policy: TrailingPolicy,
+    ) -> Parser<'f, Vec<A>, S>
+    where
+        S: Stream + 'f,
+        Self: Sized + 'f,
+    {
+        delimited_list(open, self, sep, close, policy)
+    }
+
+    /// # Combinator: `chainl1`
+    ///
+    /// Parse **one** or more current parser separated by `op`, and combine
+    /// the results with the functions returned by `op`, associating to the left.
+    ///
+    /// # Example
+    /// ```
+    /// use parsic::combinators::*;
+    /// use parsic::core::Parsable;
+    /// use parsic::primitives::{char, satisfy, CharStream};
+    ///
+    /// let num = satisfy(|ch: &char| ch.is_ascii_digit()).map(|ch| ch as i64 - '0' as i64);
+    /// let parser = num.chainl1(char('-').map(|_| |x, y| x - y));
+    ///
+    /// let mut st = CharStream::new("9-3-2");
+    /// let (res, logs) = parser.exec(&mut st);
+    ///
+    /// assert_eq!(Some(4), res);
+    /// assert_eq!(0, logs.len());
+    /// ```
+    fn chainl1<F>(self, op: impl Parsable<Stream = S, Result = F> + 'f) -> Parser<'f, A, S>
+    where
+        F: Fn(A, A) -> A + 'f,
+        S: Stream + 'f,
+        Self: Sized + 'f,
+    {
+        chainl1(self, op)
+    }
+
+    /// # Combinator: `chainr1`
+    ///
+    /// Parse **one** or more current parser separated by `op`, and combine
+    /// the results with the functions returned by `op`, associating to the right.
+    ///
+    /// # Example
+    /// ```
+    /// use parsic::combinators::*;
+    /// use parsic::core::Parsable;
+    /// use parsic::primitives::{char, satisfy, CharStream};
+    ///
+    /// let num = satisfy(|ch: &char| ch.is_ascii_digit()).map(|ch| ch as i64 - '0' as i64);
+    /// let parser = num.chainr1(char('-').map(|_| |x, y| x - y));
+    ///
+    /// let mut st = CharStream::new("9-3-2");
+    /// let (res, logs) = parser.exec(&mut st);
+    ///
+    /// assert_eq!(Some(8), res);
+    /// assert_eq!(0, logs.len());
+    /// ```
+    fn chainr1<F>(self, op: impl Parsable<Stream = S, Result = F> + 'f) -> Parser<'f, A, S>
+    where
+        F: Fn(A, A) -> A + 'f,
+        S: Stream + 'f,
+        Self: Sized + 'f,
+    {
+        chainr1(self, op)
+    }
+
+    /// # Combinator: `chainl`
+    ///
+    /// Same as `chainl1`, but returns `x` if there is no current parser at all.
+    fn chainl<F>(self, op: impl Parsable<Stream = S, Result = F> + 'f, x: A) -> Parser<'f, A, S>
+    where
+        A: Clone,
+        F: Fn(A, A) -> A + 'f,
+        S: Stream + 'f,
+        Self: Sized + 'f,
+    {
+        chainl(self, op, x)
+    }
+
+    /// # Combinator: `chainr`
+    ///
+    /// Same as `chainr1`, but returns `x` if there is no current parser at all.
+    fn chainr<F>(self, op: impl Parsable<Stream = S, Result = F> + 'f, x: A) -> Parser<'f, A, S>
+    where
+        A: Clone,
+        F: Fn(A, A) -> A + 'f,
+        S: Stream + 'f,
+        Self: Sized + 'f,
+    {
+        chainr(self, op, x)
+    }
 }
 
 impl<'f, A: 'f, S, P: Parsable<Stream = S, Result = A>> ReplicativeExt<'f, A, S> for P {}
+
+#[cfg(test)]
+mod test_replicative {
+    use crate::combinators::*;
+    use crate::core::{Msg, MsgBody, Parsable, Parser, Pos};
+    use crate::primitives::{char, satisfy, space, CharStream};
+
+    fn num<'f>() -> impl Parsable<Stream = CharStream<'f>, Result = String> {
+        satisfy(|ch: &char| ch.is_alphanumeric()).map(|ch| ch.to_string())
+    }
+
+    fn op<'f>() -> impl Parsable<Stream = CharStream<'f>, Result = impl Fn(String, String) -> String>
+    {
+        char('.').map(|_| |x, y| format!("({}.{})", x, y))
+    }
+
+    #[test]
+    fn bounded_repetition() {
+        let digits = |p: Parser<'static, Vec<char>, CharStream<'static>>, inp| {
+            let mut st = CharStream::new(inp);
+            let res = p.exec(&mut st).0;
+            (res.map(|v| v.into_iter().collect::<String>()), st.as_str())
+        };
+        let digit = || satisfy(|ch: &char| ch.is_ascii_digit());
+
+        assert_eq!((Some("123".into()), "4"), digits(digit().count(3), "1234"));
+        assert_eq!((None, "12"), digits(digit().count(3), "12"));
+        assert_eq!((Some("".into()), ""), digits(digit().count(0), ""));
+        assert_eq!(
+            (Some("1234".into()), ""),
+            digits(digit().at_least(2), "1234")
+        );
+        assert_eq!((None, "1"), digits(digit().at_least(2), "1"));
+        assert_eq!(
+            (Some("12".into()), "34"),
+            digits(digit().at_most(2), "1234")
+        );
+        assert_eq!((Some("".into()), "a"), digits(digit().at_most(2), "a"));
+        assert_eq!(
+            (Some("123".into()), "4"),
+            digits(digit().many_m_n(2, 3), "1234")
+        );
+    }
+
+    #[test]
+    fn too_few_items() {
+        let parser = satisfy(|ch: &char| ch.is_ascii_hexdigit())
+            .label("hex digit")
+            .count(4);
+        let (res, logs) = parser.exec(&mut CharStream::new("0fz"));
+
+        assert_eq!(None, res);
+        assert_eq!(
+            "unexpected 2 items, expected 4 items (row: 0, col: 0)",
+            logs.error().unwrap().to_string()
+        );
+        let last = logs.into_iter().last().unwrap();
+        assert_eq!(
+            Msg::Error(MsgBody::new(
+                "unexpected 2 items, expected 4 items",
+                Some(Pos::new(0, 0))
+            )),
+            last
+        );
+
+        let parser = satisfy(|ch: &char| ch.is_ascii_digit()).at_least(1);
+        let (res, logs) = parser.exec(&mut CharStream::new("z"));
+
+        assert_eq!(None, res);
+        assert_eq!(
+            "unexpected 0 items, expected at least 1 item (row: 0, col: 0)",
+            logs.error().unwrap().to_string()
+        );
+    }
+
+    #[test]
+    fn many_till_fail_with_grace() {
+        let parser = char('a').many_till(char(';'));
+
+        let mut st = CharStream::new("aab");
+        let (res, logs) = parser.exec(&mut st);
+
+        assert_eq!(None, res);
+        assert_eq!("aab", st.as_str());
+        assert_eq!(
+            "unexpected 'b', expected one of ';', 'a' (row: 0, col: 2)",
+            logs.error().unwrap().to_string()
+        );
+    }
+
+    #[test]
+    fn many_till_zero_progress() {
+        let parser = pure('x').many_till(char('b'));
+
+        let mut st = CharStream::new("ab");
+        let (res, logs) = parser.exec(&mut st);
+
+        assert_eq!(None, res);
+        assert_eq!("ab", st.as_str());
+        assert_eq!(
+            "unexpected empty match repeated by `many_till`, expected parser that consumes input (row: 0, col: 0)",
+            logs.error().unwrap().to_string()
+        );
+    }
+
+    #[test]
+    fn many_variants() {
+        let digit = || satisfy(|ch: &char| ch.is_ascii_digit());
+        let sum = digit()
+            .map(|ch| ch as u32 - '0' as u32)
+            .many_fold(0, |x, y| x + y);
+        let set = digit().many_collect::<std::collections::BTreeSet<_>>();
+
+        assert_eq!(Some(10), sum.exec(&mut CharStream::new("1234")).0);
+        assert_eq!(Some(0), sum.exec(&mut CharStream::new("")).0);
+        assert_eq!(
+            Some(vec!['1', '2'].into_iter().collect()),
+            set.exec(&mut CharStream::new("2121")).0
+        );
+    }
+
+    #[test]
+    fn many_variants_fail_with_grace() {
+        let pair = char('a').right(char('b'));
+        let parser = pair.many_collect::<String>().or(pure(String::new()));
+
+        let mut st = CharStream::new("ababac");
+        let (res, logs) = parser.exec(&mut st);
+
+        assert_eq!(None, res);
+        assert_eq!("ababac", st.as_str());
+        assert_eq!(1, logs.len());
+    }
+
+    #[test]
+    fn zero_progress() {
+        let parser = char('a').optional().many().or(pure(vec![]));
+
+        let mut st = CharStream::new("aab");
+        let (res, logs) = parser.exec(&mut st);
+
+        assert_eq!(None, res);
+        assert_eq!("aab", st.as_str());
+        let err = logs.error().unwrap();
+        assert_eq!(
+            "unexpected empty match repeated by `many`, expected parser that consumes input (row: 0, col: 2)",
+            err.to_string()
+        );
+        assert_eq!(Some(2), err.pos.map(|p| p.offset()));
+        assert_eq!(1, logs.len());
+    }
+
+    #[test]
+    fn zero_progress_nested() {
+        let parser = space().many().some();
+
+        let mut st = CharStream::new("  x");
+        let (res, logs) = parser.exec(&mut st);
+
+        assert_eq!(None, res);
+        assert_eq!("  x", st.as_str());
+        assert_eq!(1, logs.len());
+    }
+
+    #[test]
+    fn bounded_zero_width() {
+        let parser = pure('x').count(3);
+
+        let (res, logs) = parser.exec(&mut CharStream::new(""));
+
+        assert_eq!(Some(vec!['x'; 3]), res);
+        assert_eq!(0, logs.len());
+    }
+
+    #[test]
+    fn trailing_policies() {
+        let list = |policy| {
+            satisfy(|ch: &char| ch.is_ascii_digit()).delimited_list(
+                char('['),
+                char(','),
+                char(']'),
+                policy,
+            )
+        };
+        let parse = |policy, inp| list(policy).exec(&mut CharStream::new(inp)).0;
+
+        assert_eq!(
+            Some(vec!['1', '2']),
+            parse(TrailingPolicy::Forbidden, "[1,2]")
+        );
+        assert_eq!(None, parse(TrailingPolicy::Forbidden, "[1,2,]"));
+        assert_eq!(
+            Some(vec!['1', '2']),
+            parse(TrailingPolicy::Allowed, "[1,2]")
+        );
+        assert_eq!(
+            Some(vec!['1', '2']),
+            parse(TrailingPolicy::Allowed, "[1,2,]")
+        );
+        assert_eq!(None, parse(TrailingPolicy::Required, "[1,2]"));
+        assert_eq!(
+            Some(vec!['1', '2']),
+            parse(TrailingPolicy::Required, "[1,2,]")
+        );
+        for policy in [
+            TrailingPolicy::Forbidden,
+            TrailingPolicy::Allowed,
+            TrailingPolicy::Required,
+        ] {
+            assert_eq!(Some(vec![]), parse(policy, "[]"));
+            assert_eq!(None, parse(policy, "[,]"));
+        }
+    }
+
+    #[test]
+    fn list_errors() {
+        let parser = satisfy(|ch: &char| ch.is_ascii_digit())
+            .label("digit")
+            .delimited_list(char('['), char(','), char(']'), TrailingPolicy::Forbidden);
+
+        let (_, logs) = parser.exec(&mut CharStream::new("[1,2,]"));
+        assert_eq!(
+            "unexpected ']', expected digit (row: 0, col: 5)",
+            logs.error().unwrap().to_string()
+        );
+
+        let (_, logs) = parser.exec(&mut CharStream::new("[1,2;"));
+        assert_eq!(
+            "unexpected ';', expected one of ',', ']' (row: 0, col: 4)",
+            logs.error().unwrap().to_string()
+        );
+    }
+
+    #[test]
+    fn list_zero_progress() {
+        let parser = sep_by(pure(1), pure(()));
+
+        let mut st = CharStream::new("1");
+        let (res, logs) = parser.exec(&mut st);
+
+        assert_eq!(None, res);
+        assert_eq!("1", st.as_str());
+        assert_eq!(
+            "unexpected empty match repeated by `sep_by`, expected parser that consumes input (row: 0, col: 0)",
+            logs.error().unwrap().to_string()
+        );
+    }
+
+    #[test]
+    fn sep_by_empty() {
+        let parser = sep_by(satisfy(|ch: &char| ch.is_ascii_digit()), char(','));
+
+        let mut st = CharStream::new(",1");
+        let (res, logs) = parser.exec(&mut st);
+
+        assert_eq!(Some(vec![]), res);
+        assert_eq!(",1", st.as_str());
+        assert_eq!(0, logs.len());
+    }
+
+    #[test]
+    fn associativity() {
+        let left = chainl1(num(), op());
+        let right = chainr1(num(), op());
+
+        assert_eq!(
+            Some("((a.b).c)".to_string()),
+            left.exec(&mut CharStream::new("a.b.c")).0
+        );
+        assert_eq!(
+            Some("(a.(b.c))".to_string()),
+            right.exec(&mut CharStream::new("a.b.c")).0
+        );
+        assert_eq!(
+            Some("a".to_string()),
+            right.exec(&mut CharStream::new("a")).0
+        );
+    }
+
+    #[test]
+    fn chain_with_default() {
+        let parser = chainl(num(), op(), "none".to_string());
+
+        let mut st = CharStream::new(".a");
+        let (res, logs) = parser.exec(&mut st);
+
+        assert_eq!(Some("none".to_string()), res);
+        assert_eq!(".a", st.as_str());
+        assert_eq!(0, logs.len());
+        assert_eq!(
+            Some("(a.b)".to_string()),
+            chainr(num(), op(), String::new())
+                .exec(&mut CharStream::new("a.b"))
+                .0
+        );
+    }
+
+    #[test]
+    fn fail_with_grace() {
+        let parser = chainr1(num(), op());
+
+        let mut st = CharStream::new("a.");
+        let (res, logs) = parser.exec(&mut st);
+
+        assert_eq!(None, res);
+        assert_eq!("a.", st.as_str());
+        assert_eq!(1, logs.len());
+    }
+}
@@ -1,5 +1,5 @@
 use crate::combinators::FunctorExt;
-use crate::core::{return_none, Parsable, Parser};
+use crate::core::{return_none, Parsable, Parser, Stream};
 
 /// # Combinator: `and` (function ver.)
 ///
@@ -23,7 +23,7 @@ use crate::core::{return_none, Parsable, Parser};
 /// assert_eq!("C", st.as_str());
 /// assert_eq!(0, logs.len());
 /// ```
-pub fn and<'f, A: 'f, B: 'f, S: Clone>(
+pub fn and<'f, A: 'f, B: 'f, S: Stream>(
     p1: impl Parsable<Stream = S, Result = A> + 'f,
     p2: impl Parsable<Stream = S, Result = B> + 'f,
 ) -> Parser<'f, (A, B), S> {
@@ -31,7 +31,7 @@ pub fn and<'f, A: 'f, B: 'f, S: Clone>(
         let st = stream.clone();
         p1.parse(stream, logger)
             .and_then(|x| p2.parse(stream, logger).map(|y| (x, y)))
-            .or_else(|| return_none(stream, &st))
+            .or_else(|| return_none(stream, &st, logger))
     })
 }
 
@@ -57,7 +57,7 @@ pub fn and<'f, A: 'f, B: 'f, S: Clone>(
 /// assert_eq!("C", st.as_str());
 /// assert_eq!(0, logs.len());
 /// ```
-pub fn left<'f, A: 'f, B: 'f, S: Clone + 'f>(
+pub fn left<'f, A: 'f, B: 'f, S: Stream + 'f>(
     p1: impl Parsable<Stream = S, Result = A> + 'f,
     p2: impl Parsable<Stream = S, Result = B> + 'f,
 ) -> Parser<'f, A, S> {
@@ -86,7 +86,7 @@ pub fn left<'f, A: 'f, B: 'f, S: Clone + 'f>(
 /// assert_eq!("C", st.as_str());
 /// assert_eq!(0, logs.len());
 /// ```
-pub fn right<'f, A: 'f, B: 'f, S: Clone + 'f>(
+pub fn right<'f, A: 'f, B: 'f, S: Stream + 'f>(
     p1: impl Parsable<Stream = S, Result = A> + 'f,
     p2: impl Parsable<Stream = S, Result = B> + 'f,
 ) -> Parser<'f, B, S> {
@@ -115,7 +115,7 @@ pub fn right<'f, A: 'f, B: 'f, S: Clone + 'f>(
 /// assert_eq!("", st.as_str());
 /// assert_eq!(0, logs.len());
 /// ```
-pub fn mid<'f, A: 'f, B: 'f, C: 'f, S: Clone + 'f>(
+pub fn mid<'f, A: 'f, B: 'f, C: 'f, S: Stream + 'f>(
     p1: impl Parsable<Stream = S, Result = A> + 'f,
     p2: impl Parsable<Stream = S, Result = B> + 'f,
     p3: impl Parsable<Stream = S, Result = C> + 'f,
@@ -148,7 +148,7 @@ pub trait SequentialExt<'f, A: 'f, S>: Parsable<Stream = S, Result = A> {
     /// ```
     fn and<B: 'f>(self, p: impl Parsable<Stream = S, Result = B> + 'f) -> Parser<'f, (A, B), S>
     where
-        S: Clone,
+        S: Stream,
         Self: Sized + 'f,
     {
         and(self, p)
@@ -178,7 +178,7 @@ pub trait SequentialExt<'f, A: 'f, S>: Parsable<Stream = S, Result = A> {
     /// ```
     fn left<B: 'f>(self, p: impl Parsable<Stream = S, Result = B> + 'f) -> Parser<'f, A, S>
     where
-        S: Clone + 'f,
+        S: Stream + 'f,
         Self: Sized + 'f,
     {
         left(self, p)
@@ -208,7 +208,7 @@ pub trait SequentialExt<'f, A: 'f, S>: Parsable<Stream = S, Result = A> {
     /// ```
     fn right<B: 'f>(self, p: impl Parsable<Stream = S, Result = B> + 'f) -> Parser<'f, B, S>
     where
-        S: Clone + 'f,
+        S: Stream + 'f,
         Self: Sized + 'f,
     {
         right(self, p)
@@ -242,7 +242,7 @@ pub trait SequentialExt<'f, A: 'f, S>: Parsable<Stream = S, Result = A> {
         p2: impl Parsable<Stream = S, Result = C> + 'f,
     ) -> Parser<'f, B, S>
     where
-        S: Clone + 'f,
+        S: Stream + 'f,
         Self: Sized + 'f,
     {
         mid(self, p1, p2)
@@ -259,7 +259,7 @@ mod test_sequential {
 
     #[test]
     fn different_type_ok() {
-        let parser = satisfy(|&ch| ch.is_digit(10))
+        let parser = satisfy(|&ch: &char| ch.is_ascii_digit())
             .map_option(|ch| ch.to_digit(10))
             .and(char('A'));
 
@@ -0,0 +1,212 @@
+use crate::core::line_index::line_starts;
+use crate::core::logger::*;
+
+/// # Struct `Renderer`
+/// Renders log messages against the source they were produced from,
+/// showing the file name, 1-based line and column, the offending line
+/// and a caret under the position.
+///
+/// # Example
+/// ```
+/// use parsic::combinators::*;
+/// use parsic::core::{Parsable, Renderer};
+/// use parsic::primitives::{char, CharStream};
+///
+/// let src = "ab";
+/// let parser = char('a').and(char('c'));
+///
+/// let mut st = CharStream::new(src);
+/// let (_, logs) = parser.exec(&mut st);
+///
+/// assert_eq!(
+///     "error: unexpected 'b', expected 'c'\n \
+///      --> input.txt:1:2\n  \
+///       |\n\
+///      1 | ab\n  \
+///       |  ^\n",
+///     Renderer::new("input.txt", src).render(&logs)
+/// );
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Renderer<'s> {
+    name: &'s str,
+    source: &'s str,
+    color: bool,
+}
+
+impl<'s> Renderer<'s> {
+    pub fn new(name: &'s str, source: &'s str) -> Self {
+        Self {
+            name,
+            source,
+            color: false,
+        }
+    }
+
+    /// Enable or disable ANSI colours for severities
+    pub fn with_color(self, color: bool) -> Self {
+        Self { color, ..self }
+    }
+
+    /// Render all messages of given logger
+    pub fn render(&self, logger: &ParseLogger) -> String {
+        logger.into_iter().map(|msg| self.render_msg(msg)).collect()
+    }
+
+    /// Render a single message, underlining one character at its position
+    pub fn render_msg(&self, msg: &Msg) -> String {
+        let body = match msg {
+            Msg::Info(x) | Msg::Warn(x) | Msg::Error(x) => x,
+        };
+        let span = body.pos.map(|p| Span::new(p, p));
+        self.render_span(msg, span)
+    }
+
+    /// Render a message, underlining given span of the source
+    pub fn render_span(&self, msg: &Msg, span: Option<Span>) -> String {
+        let (level, color, body) = match msg {
+            Msg::Info(x) => ("info", "\x1b[1;36m", x),
+            Msg::Warn(x) => ("warning", "\x1b[1;33m", x),
+            Msg::Error(x) => ("error", "\x1b[1;31m", x),
+        };
+        let mut res = format!("{}: {}\n", self.paint(color, level), body.msg);
+        let span = match span {
+            Some(span) => span,
+            None => {
+                res += &format!(" {} {}\n", self.paint("\x1b[1;34m", "-->"), self.name);
+                return res;
+            }
+        };
+
+        let start = self.clamp(span.start.offset());
+        let end = self.clamp(span.end.offset()).max(start);
+        let (row, line_start) = line_starts(self.source)
+            .take_while(|&i| i <= start)
+            .fold((0, 0), |(row, _), i| (row + 1, i));
+        let line_end = self.source[start..]
+            .find(['\n', '\r'])
+            .map_or(self.source.len(), |i| start + i);
+        let line = self.source[line_start..line_end].trim_end_matches('\r');
+        let col = self.source[line_start..start].chars().count() + 1;
+
+        // Keep tabs so that the caret lines up with the source line
+        let pad = self.source[line_start..start]
+            .chars()
+            .map(|ch| if ch == '\t' { '\t' } else { ' ' })
+            .collect::<String>();
+        let width = self.source[start..end.min(line_end)].chars().count().max(1);
+
+        let gutter = " ".repeat(row.to_string().len());
+        let bar = self.paint("\x1b[1;34m", "|");
+        res += &format!(
+            "{}{} {}:{}:{}\n",
+            gutter,
+            self.paint("\x1b[1;34m", "-->"),
+            self.name,
+            row,
+            col
+        );
+        res += &format!("{} {}\n", gutter, bar);
+        res += &format!(
+            "{} {} {}\n",
+            self.paint("\x1b[1;34m", &row.to_string()),
+            bar,
+            line
+        );
+        res += &format!(
+            "{} {} {}{}\n",
+            gutter,
+            bar,
+            pad,
+            self.paint(color, &"^".repeat(width))
+        );
+        res
+    }
+
+    /// Helper function that clamps an offset to a char boundary of the source
+    fn clamp(&self, offset: usize) -> usize {
+        let mut offset = offset.min(self.source.len());
+        while !self.source.is_char_boundary(offset) {
+            offset -= 1;
+        }
+        offset
+    }
+
+    /// Helper function that colours given text if colours are enabled
+    fn paint(&self, color: &str, text: &str) -> String {
+        match self.color {
+            true => format!("{}{}\x1b[0m", color, text),
+            false => text.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_diagnostic {
+    use crate::core::*;
+
+    fn error(msg: &str, offset: usize) -> Msg {
+        Msg::Error(MsgBody::new(msg, Some(Pos::from_offset(offset))))
+    }
+
+    #[test]
+    fn render_multi_line() {
+        let src = "let x = 1;\r\nlet y = é?;\n";
+        let out = Renderer::new("main.rs", src).render_msg(&error("oops", 22));
+
+        assert_eq!(
+            "error: oops\n \
+             --> main.rs:2:10\n  \
+              |\n\
+             2 | let y = é?;\n  \
+              |          ^\n",
+            out
+        );
+    }
+
+    #[test]
+    fn render_cr_line_breaks() {
+        let src = "a\rbc\r\rd";
+        let out = Renderer::new("a", src).render_msg(&error("oops", 3));
+
+        assert!(out.contains("--> a:2:2\n"));
+        assert!(out.contains("2 | bc\n"));
+
+        let out = Renderer::new("a", src).render_msg(&error("oops", 6));
+        assert!(out.contains("--> a:4:1\n"));
+        assert!(out.contains("4 | d\n"));
+    }
+
+    #[test]
+    fn render_span_with_tabs() {
+        let src = "\tfoo bar";
+        let span = Span::new(Pos::from_offset(1), Pos::from_offset(4));
+        let msg = Msg::Warn(MsgBody::new("unused", None));
+        let out = Renderer::new("a", src).render_span(&msg, Some(span));
+
+        assert!(out.starts_with("warning: unused\n"));
+        assert!(out.ends_with("1 | \tfoo bar\n  | \t^^^\n"));
+    }
+
+    #[test]
+    fn render_at_end_and_without_pos() {
+        let src = "ab";
+        let out = Renderer::new("a", src).render_msg(&error("eof", 2));
+        assert!(out.contains("--> a:1:3\n"));
+
+        let msg = Msg::Info(MsgBody::new("note", None));
+        assert_eq!(
+            "info: note\n --> a\n",
+            Renderer::new("a", src).render_msg(&msg)
+        );
+    }
+
+    #[test]
+    fn render_with_color() {
+        let out = Renderer::new("a", "x")
+            .with_color(true)
+            .render_msg(&error("bad", 0));
+
+        assert!(out.starts_with("\x1b[1;31merror\x1b[0m: bad\n"));
+    }
+}
@@ -0,0 +1,180 @@
+/// # Enum `Encoding`
+/// Units in which columns are counted.
+///
+/// - `Utf8`: bytes
+/// - `Utf16`: UTF-16 code units, as used by the Language Server Protocol
+/// - `Utf32`: Unicode scalar values, i.e. `char`s
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Encoding {
+    Utf8,
+    Utf16,
+    Utf32,
+}
+
+impl Encoding {
+    /// Return the width of given character in this encoding
+    fn width(&self, ch: char) -> usize {
+        match self {
+            Encoding::Utf8 => ch.len_utf8(),
+            Encoding::Utf16 => ch.len_utf16(),
+            Encoding::Utf32 => 1,
+        }
+    }
+}
+
+/// # Struct `LineCol`
+/// A 0-based line and column, the column counted in some `Encoding`.
+#[derive(Clone, Copy, Default, Debug, Eq, PartialEq)]
+pub struct LineCol {
+    pub line: usize,
+    pub col: usize,
+}
+
+impl LineCol {
+    pub fn new(line: usize, col: usize) -> Self {
+        Self { line, col }
+    }
+}
+
+/// # Struct `LineIndex`
+/// Converts byte offsets of a source into lines and columns and back.
+/// `\n`, `\r\n` and a lone `\r` are all treated as a single line break.
+///
+/// # Example
+/// ```
+/// use parsic::core::{Encoding, LineCol, LineIndex};
+///
+/// let index = LineIndex::new("let a = 1;\nlet 𝔟 = 2;");
+///
+/// // `=` after the non-BMP character `𝔟`
+/// assert_eq!(LineCol::new(1, 9), index.line_col(20, Encoding::Utf8));
+/// assert_eq!(LineCol::new(1, 7), index.line_col(20, Encoding::Utf16));
+/// assert_eq!(LineCol::new(1, 6), index.line_col(20, Encoding::Utf32));
+/// assert_eq!(Some(20), index.offset(LineCol::new(1, 7), Encoding::Utf16));
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LineIndex<'s> {
+    text: &'s str,
+    starts: Vec<usize>,
+}
+
+impl<'s> LineIndex<'s> {
+    /// Build a line index of given source
+    pub fn new(text: &'s str) -> Self {
+        Self {
+            text,
+            starts: line_starts(text).collect(),
+        }
+    }
+
+    /// Return number of lines
+    pub fn line_count(&self) -> usize {
+        self.starts.len()
+    }
+
+    /// Convert a byte offset into a line and column. Offsets past the end
+    /// or inside a character are moved back to the closest character.
+    pub fn line_col(&self, offset: usize, enc: Encoding) -> LineCol {
+        let mut offset = offset.min(self.text.len());
+        while !self.text.is_char_boundary(offset) {
+            offset -= 1;
+        }
+        let line = self.starts.partition_point(|&s| s <= offset) - 1;
+        let col = self.text[self.starts[line]..offset]
+            .chars()
+            .map(|ch| enc.width(ch))
+            .sum();
+        LineCol::new(line, col)
+    }
+
+    /// Convert a line and column back into a byte offset, returning `None`
+    /// if the line does not exist or the column is not on a character
+    /// boundary within the line.
+    pub fn offset(&self, lc: LineCol, enc: Encoding) -> Option<usize> {
+        let start = *self.starts.get(lc.line)?;
+        let end = self.starts.get(lc.line + 1).map_or(self.text.len(), |&x| x);
+        let line = self.text[start..end].trim_end_matches(['\n', '\r']);
+        let mut col = 0;
+        for (i, ch) in line.char_indices() {
+            if col == lc.col {
+                return Some(start + i);
+            }
+            col += enc.width(ch);
+        }
+        match col == lc.col {
+            true => Some(start + line.len()),
+            false => None,
+        }
+    }
+}
+
+/// Helper function that returns the byte offsets where lines of `text`
+/// start. `\n`, `\r\n` and a lone `\r` are all a single line break.
+pub(crate) fn line_starts(text: &str) -> impl Iterator<Item = usize> + '_ {
+    let bytes = text.as_bytes();
+    std::iter::once(0).chain(bytes.iter().enumerate().filter_map(move |(i, &b)| match b {
+        b'\n' => Some(i + 1),
+        b'\r' if bytes.get(i + 1) != Some(&b'\n') => Some(i + 1),
+        _ => None,
+    }))
+}
+
+#[cfg(test)]
+mod test_line_index {
+    use crate::core::*;
+
+    #[test]
+    fn line_breaks() {
+        let index = LineIndex::new("a\nb\r\nc\rd");
+
+        assert_eq!(4, index.line_count());
+        assert_eq!(LineCol::new(0, 1), index.line_col(1, Encoding::Utf8));
+        assert_eq!(LineCol::new(1, 0), index.line_col(2, Encoding::Utf8));
+        assert_eq!(LineCol::new(1, 2), index.line_col(4, Encoding::Utf8));
+        assert_eq!(LineCol::new(2, 0), index.line_col(5, Encoding::Utf8));
+        assert_eq!(LineCol::new(3, 1), index.line_col(99, Encoding::Utf8));
+    }
+
+    #[test]
+    fn round_trip() {
+        let src = "héllo\n世界 🦀!\n";
+        let index = LineIndex::new(src);
+
+        for enc in [Encoding::Utf8, Encoding::Utf16, Encoding::Utf32] {
+            for (offset, _) in src.char_indices() {
+                let lc = index.line_col(offset, enc);
+                assert_eq!(Some(offset), index.offset(lc, enc));
+            }
+        }
+        assert_eq!(LineCol::new(1, 5), index.line_col(18, Encoding::Utf16));
+        assert_eq!(LineCol::new(1, 4), index.line_col(18, Encoding::Utf32));
+    }
+
+    #[test]
+    fn invalid_line_col() {
+        let index = LineIndex::new("🦀\nx");
+
+        assert_eq!(None, index.offset(LineCol::new(0, 1), Encoding::Utf16));
+        assert_eq!(None, index.offset(LineCol::new(1, 2), Encoding::Utf32));
+        assert_eq!(None, index.offset(LineCol::new(2, 0), Encoding::Utf32));
+        assert_eq!(None, index.offset(LineCol::new(0, 2), Encoding::Utf32));
+        assert_eq!(LineCol::new(0, 0), index.line_col(3, Encoding::Utf8));
+    }
+
+    #[test]
+    fn convert_msg_pos() {
+        use crate::combinators::*;
+        use crate::primitives::{char, literal, CharStream};
+
+        let src = "é🦀\n🦀x";
+        let parser = literal("é🦀\n🦀").and(char('y'));
+        let (_, logs) = parser.exec(&mut CharStream::new(src));
+
+        let pos = logs.error().unwrap().pos.unwrap();
+        let index = LineIndex::new(src);
+        assert_eq!(
+            LineCol::new(1, 2),
+            index.line_col(pos.offset(), Encoding::Utf16)
+        );
+    }
+}
@@ -1,22 +1,119 @@
+use std::cell::Cell;
+use std::cmp::Ordering;
+use std::collections::HashSet;
 use std::fmt::{Display, Formatter, Result};
+use std::rc::Rc;
 
 /// # Struct `Pos`
-/// Data structure for parsing position.
+/// Data structure for parsing position. A position always carries
+/// the offset from the start of input, both in bytes and in items
+/// (characters for streams of characters), and optionally a row and
+/// a column for streams that have a notion of lines.
 #[derive(Clone, Copy, Default, Debug, Eq, PartialEq)]
-pub struct Pos(usize, usize);
+pub struct Pos {
+    offset: usize,
+    chars: usize,
+    line: Option<(usize, usize)>,
+}
 
 impl Pos {
     pub fn new(row: usize, col: usize) -> Self {
-        Self(row, col)
+        Self {
+            offset: 0,
+            chars: 0,
+            line: Some((row, col)),
+        }
+    }
+    pub fn from_offset(offset: usize) -> Self {
+        Self {
+            offset,
+            chars: offset,
+            line: None,
+        }
+    }
+    pub fn with_offset(&self, offset: usize) -> Self {
+        Self { offset, ..*self }
+    }
+    pub fn with_char_offset(&self, chars: usize) -> Self {
+        Self { chars, ..*self }
     }
     pub fn add(&self, d_row: usize, d_col: usize) -> Self {
-        Self(self.0 + d_row, self.1 + d_col)
+        Self {
+            line: Some((self.row() + d_row, self.col() + d_col)),
+            ..*self
+        }
     }
     pub fn row(&self) -> usize {
-        self.0
+        self.line.map_or(0, |(r, _)| r)
     }
     pub fn col(&self) -> usize {
-        self.1
+        self.line.map_or(0, |(_, c)| c)
+    }
+    /// Return the offset in bytes
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+    /// Return the offset in items, i.e. characters for streams of characters
+    pub fn char_offset(&self) -> usize {
+        self.chars
+    }
+    /// Check if the position has row and column
+    pub fn has_line(&self) -> bool {
+        self.line.is_some()
+    }
+}
+
+impl Display for Pos {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self.line {
+            Some((r, c)) => write!(f, "row: {}, col: {}", r, c),
+            None => write!(f, "offset: {}", self.offset),
+        }
+    }
+}
+
+/// # Struct `Span`
+/// Data structure for a range of input, starting at `start`
+/// (inclusive) and ending at `end` (exclusive).
+#[derive(Clone, Copy, Default, Debug, Eq, PartialEq)]
+pub struct Span {
+    pub start: Pos,
+    pub end: Pos,
+}
+
+impl Span {
+    pub fn new(start: Pos, end: Pos) -> Self {
+        Self { start, end }
+    }
+    /// Return the range of byte offsets
+    pub fn range(&self) -> std::ops::Range<usize> {
+        self.start.offset()..self.end.offset()
+    }
+    /// Return length in bytes
+    pub fn len(&self) -> usize {
+        self.end.offset().saturating_sub(self.start.offset())
+    }
+    /// Check if the span is empty
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// # Struct `Spanned`
+/// A value along with the span of input it was parsed from.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Spanned<A> {
+    pub value: A,
+    pub span: Span,
+}
+
+impl<A> Spanned<A> {
+    pub fn new(value: A, span: Span) -> Self {
+        Self { value, span }
+    }
+    /// Map the value, keeping the span
+    pub fn map<B>(self, f: impl FnOnce(A) -> B) -> Spanned<B> {
+        Spanned::new(f(self.value), self.span)
     }
 }
 
@@ -38,7 +135,7 @@ impl Display for Msg {
         };
 
         let pos_text = match body.pos {
-            Some(Pos(r, c)) => format!("(at row: {}, col: {})", r, c),
+            Some(pos) => format!("(at {})", pos),
             _ => "".to_string(),
         };
 
@@ -63,11 +160,116 @@ impl MsgBody {
     }
 }
 
+/// # Struct `ParseError`
+/// Data structure for a structured parse error, describing what
+/// was found at a position and what was expected instead.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ParseError {
+    pub pos: Option<Pos>,
+    pub unexpected: Option<String>,
+    pub expected: Vec<String>,
+}
+
+impl ParseError {
+    pub fn new(pos: Option<Pos>) -> Self {
+        Self {
+            pos,
+            ..Self::default()
+        }
+    }
+
+    /// Set the unexpected item
+    pub fn with_unexpected(mut self, item: &str) -> Self {
+        self.unexpected = Some(item.to_string());
+        self
+    }
+
+    /// Add an expected item
+    pub fn with_expected(mut self, item: &str) -> Self {
+        if !self.expected.iter().any(|x| x == item) {
+            self.expected.push(item.to_string());
+        }
+        self
+    }
+
+    /// Merge with another error. The error at the furthest position
+    /// wins, and expected items are merged if both are at the same position.
+    pub fn merge(self, other: Self) -> Self {
+        let offset = |e: &Self| e.pos.map(|p| p.offset());
+        match offset(&self).cmp(&offset(&other)) {
+            Ordering::Less => other,
+            Ordering::Greater => self,
+            Ordering::Equal => {
+                let unexpected = self.unexpected.clone().or(other.unexpected);
+                let mut res = other.expected.iter().fold(self, |e, x| e.with_expected(x));
+                res.unexpected = unexpected;
+                res
+            }
+        }
+    }
+
+    /// Describe what was found and what was expected, without the
+    /// position, which log messages carry separately
+    pub fn message(&self) -> String {
+        let unexpected = self
+            .unexpected
+            .as_ref()
+            .map(|x| format!("unexpected {}", x));
+        let expected = match self.expected.len() {
+            0 => None,
+            1 => Some(format!("expected {}", self.expected[0])),
+            _ => Some(format!("expected one of {}", self.expected.join(", "))),
+        };
+        match (unexpected, expected) {
+            (Some(u), Some(e)) => format!("{}, {}", u, e),
+            (Some(x), None) | (None, Some(x)) => x,
+            (None, None) => "unexpected input".to_string(),
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self.pos {
+            Some(pos) => write!(f, "{} ({})", self.message(), pos),
+            None => write!(f, "{}", self.message()),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Identity of a parsing session, used by stateful parsers like
+/// `memo` to tell whether their state belongs to the current session.
+/// It also counts the rounds of `fix_left_rec` started in the session.
+/// All sessions compare equal so that loggers could still be compared.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Session(pub(crate) Rc<Cell<usize>>);
+
+impl PartialEq for Session {
+    fn eq(&self, _: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for Session {}
+
 /// # Struct `ParseLogger`
 /// An implementation of parse logger that stores logs.
+///
+/// Besides logs, the logger keeps track of the furthest `ParseError`
+/// so far, and whether the current alternative has consumed any input
+/// or passed a `cut`, which decides if a failure could be recovered by
+/// trying other alternatives.
 #[derive(Clone, Debug, Eq, PartialEq, Default)]
 pub struct ParseLogger {
     pub stack: Vec<Msg>,
+    pub(crate) error: Option<ParseError>,
+    pub(crate) consumed: bool,
+    pub(crate) cut: bool,
+    pub(crate) replayed: HashSet<(usize, usize)>,
+    pub(crate) session: Session,
+    pub(crate) round: usize,
 }
 
 impl ParseLogger {
@@ -87,6 +289,88 @@ impl ParseLogger {
         self.add(msg);
     }
 
+    /// Merge given error into the furthest error so far,
+    /// and log the merged error
+    pub fn fail(&mut self, err: ParseError) {
+        self.merge(err);
+        if let Some(e) = &self.error {
+            self.with(Msg::Error(MsgBody::new(&e.message(), e.pos)));
+        }
+    }
+
+    /// Log given error as a fatal one, which replaces the furthest
+    /// error so far and stops other alternatives from being tried
+    pub(crate) fn fatal(&mut self, err: ParseError) {
+        self.error = None;
+        self.fail(err);
+        self.cut = true;
+    }
+
+    /// Merge given error into the furthest error so far without logging
+    pub(crate) fn merge(&mut self, err: ParseError) {
+        self.error = Some(match self.error.take() {
+            Some(e) => e.merge(err),
+            None => err,
+        });
+    }
+
+    /// Restore a previously saved logger after a failure that is
+    /// recovered from, keeping the furthest error so far
+    pub(crate) fn restore(&mut self, bak: ParseLogger) {
+        let err = self.error.take();
+        *self = bak;
+        self.error = err;
+    }
+
+    /// Create a logger for a nested parse in the same session
+    pub(crate) fn nested(&self) -> Self {
+        Self {
+            session: self.session.clone(),
+            round: self.round,
+            ..Self::default()
+        }
+    }
+
+    /// Create a logger for a nested parse in a new round of growing a
+    /// left-recursive seed, whose results differ from previous rounds
+    pub(crate) fn next_round(&self) -> Self {
+        let round = self.session.0.get() + 1;
+        self.session.0.set(round);
+        Self {
+            round,
+            ..self.nested()
+        }
+    }
+
+    /// Merge the state of a logger created by `nested` after the nested
+    /// parse, as if the parse had been done with this logger
+    pub(crate) fn absorb(&mut self, inner: ParseLogger, ok: bool) {
+        self.consumed |= inner.consumed;
+        self.cut |= inner.cut;
+        self.replayed.extend(inner.replayed);
+        match ok {
+            true => self.stack.extend(inner.stack),
+            false if !inner.stack.is_empty() => self.stack = inner.stack,
+            _ => (),
+        }
+        if let Some(err) = inner.error {
+            let msg = Msg::Error(MsgBody::new(&err.message(), err.pos));
+            self.merge(err);
+            // Render the merged error in place of the nested one
+            if !ok && self.stack.last() == Some(&msg) {
+                self.stack.pop();
+                if let Some(e) = &self.error {
+                    self.add(Msg::Error(MsgBody::new(&e.message(), e.pos)));
+                }
+            }
+        }
+    }
+
+    /// Return the furthest error so far
+    pub fn error(&self) -> Option<&ParseError> {
+        self.error.as_ref()
+    }
+
     /// Return number of logs
     pub fn len(&self) -> usize {
         self.stack.len()
@@ -96,6 +380,46 @@ impl ParseLogger {
     pub fn is_empty(&self) -> bool {
         self.stack.is_empty()
     }
+
+    /// Mark that input has been consumed. Combinators detect consumed
+    /// input from the stream themselves, so this is only needed to
+    /// commit to the current alternative without moving the stream.
+    pub fn consume(&mut self) {
+        self.consumed = true;
+    }
+
+    /// Record that the stream is moved from index `from` to `to` by
+    /// replaying a seed of `fix_left_rec`, which does not consume input.
+    /// The record is dropped when the round of growing the seed ends.
+    pub(crate) fn replay(&mut self, from: usize, to: usize) {
+        self.replayed.insert((from, to));
+    }
+
+    /// Check if moving the stream from index `from` to `to` has consumed input
+    pub(crate) fn has_consumed(&self, from: usize, to: usize) -> bool {
+        from != to && !self.replayed.contains(&(from, to))
+    }
+
+    /// Check if a failure happened after consuming input or
+    /// passing a `cut`, in which case other alternatives are not tried
+    pub fn is_committed(&self) -> bool {
+        self.consumed || self.cut
+    }
+
+    /// Enter a new alternative, returning the state of the outer one
+    pub(crate) fn enter(&mut self) -> (bool, bool) {
+        let outer = (self.consumed, self.cut);
+        self.consumed = false;
+        self.cut = false;
+        outer
+    }
+
+    /// Leave current alternative and merge its state into the outer one.
+    /// A `cut` only outlives the alternative it belongs to if it fails.
+    pub(crate) fn leave(&mut self, outer: (bool, bool), ok: bool) {
+        self.consumed |= outer.0;
+        self.cut = outer.1 || (self.cut && !ok);
+    }
 }
 
 /// Implement IntoInterator for ParseLogger
@@ -1,11 +1,23 @@
+mod diagnostic;
+mod line_index;
 mod logger;
 mod ops;
 mod parser;
+mod stream;
 
-pub use crate::core::{self, logger::*, ops::*, parser::*};
+pub use crate::core::{self, diagnostic::*, line_index::*, logger::*, parser::*, stream::*};
 
-/// Helper function that undo changes to stream
-pub(crate) fn return_none<S: Clone, T>(cur: &mut S, bak: &S) -> Option<T> {
+/// Helper function that undo changes to stream after a failure.
+/// Input consumed since `bak` is recorded in the logger, so that
+/// alternatives are not tried after the failure.
+pub(crate) fn return_none<S: Stream, T>(
+    cur: &mut S,
+    bak: &S,
+    logger: &mut ParseLogger,
+) -> Option<T> {
+    if logger.has_consumed(bak.index(), cur.index()) {
+        logger.consume();
+    }
     *cur = bak.to_owned();
     None
 }
@@ -1,6 +1,7 @@
 use crate::combinators::*;
-use crate::core::{Parsable, Parser};
-use std::ops::{BitAnd, BitOr, Mul, Shl, Shr};
+use crate::core::{Parsable, Parser, Stream};
+use std::fmt::Debug;
+use std::ops::{BitAnd, BitOr, Mul, Not, Shl, Shr};
 
 /// # Overload Shl `<<` to `left` combinator
 ///
@@ -24,7 +25,7 @@ use std::ops::{BitAnd, BitOr, Mul, Shl, Shr};
 impl<'f, A: 'f, B: 'f, S, P> Shl<P> for Parser<'f, A, S>
 where
     P: Parsable<Stream = S, Result = B> + 'f,
-    S: Clone + 'f,
+    S: Stream + 'f,
     Self: Sized + 'f,
 {
     type Output = Parser<'f, A, S>;
@@ -55,7 +56,7 @@ where
 impl<'f, A: 'f, B: 'f, S, P> Shr<P> for Parser<'f, A, S>
 where
     P: Parsable<Stream = S, Result = B> + 'f,
-    S: Clone + 'f,
+    S: Stream + 'f,
     Self: Sized + 'f,
 {
     type Output = Parser<'f, B, S>;
@@ -86,7 +87,7 @@ where
 impl<'f, A: 'f, S, P> BitOr<P> for Parser<'f, A, S>
 where
     P: Parsable<Stream = S, Result = A> + 'f,
-    S: Clone + 'f,
+    S: Stream + 'f,
     Self: Sized + 'f,
 {
     type Output = Parser<'f, A, S>;
@@ -117,7 +118,7 @@ where
 impl<'f, A: 'f, B: 'f, S, P> BitAnd<P> for Parser<'f, A, S>
 where
     P: Parsable<Stream = S, Result = B> + 'f,
-    S: Clone + 'f,
+    S: Stream + 'f,
     Self: Sized + 'f,
 {
     type Output = Parser<'f, (A, B), S>;
@@ -150,7 +151,7 @@ impl<'f, A: 'f, B: 'f, F, S, P> Mul<P> for Parser<'f, F, S>
 where
     F: Fn(A) -> B + 'f,
     P: Parsable<Stream = S, Result = A> + 'f,
-    S: Clone + 'f,
+    S: Stream + 'f,
     Self: Sized + 'f,
 {
     type Output = Parser<'f, B, S>;
@@ -160,6 +161,37 @@ where
     }
 }
 
+/// # Overload operator `!` to `not_followed_by` combinator
+///
+/// `!p` ~ `not_followed_by(p)`
+///
+/// ## Example
+/// ```
+/// use parsic::combinators::*;
+/// use parsic::core::{Parsable, Parser};
+/// use parsic::primitives::{literal, satisfy, CharStream};
+///
+/// let parser = literal("if") << !satisfy(|ch: &char| ch.is_alphanumeric());
+///
+/// let mut st = CharStream::new("iffy");
+/// let (res, logs) = parser.exec(&mut st);
+///
+/// assert_eq!(None, res);
+/// assert_eq!("iffy", st.as_str());
+/// assert_eq!(1, logs.len());
+/// ```
+impl<'f, A: 'f, S> Not for Parser<'f, A, S>
+where
+    S: Stream + 'f,
+    S::Item: Debug,
+{
+    type Output = Parser<'f, (), S>;
+
+    fn not(self) -> Self::Output {
+        not_followed_by(self)
+    }
+}
+
 #[cfg(test)]
 mod test_ops {
     use crate::combinators::*;
@@ -174,16 +206,16 @@ mod test_ops {
         // uint     := digit { digit }
         // digit    := '0' | '1' | ... | '9'
         let expr = fix(move |expr| {
-            let digit = satisfy(|&ch| ch.is_digit(10));
+            let digit = satisfy(|&ch: &char| ch.is_ascii_digit());
             let uint = digit
                 .some()
                 .map(|v| v.iter().collect::<String>().parse::<u64>().unwrap());
             let factor = char('(') >> expr.clone() << char(')') | uint;
             let term = fix(move |term| {
-                (factor.clone() << char('*') & term.clone()).map(|(v1, v2)| v1 * v2)
+                attempt((factor.clone() << char('*') & term.clone()).map(|(v1, v2)| v1 * v2))
                     | factor.clone()
             });
-            (term.clone() << char('+') & expr).map(|(v1, v2)| v1 + v2) | term
+            attempt((term.clone() << char('+') & expr).map(|(v1, v2)| v1 + v2)) | term
         });
 
         let mut st = CharStream::new("1+2*(3+4)");
@@ -1,10 +1,14 @@
 use crate::core::logger::*;
+use crate::core::Stream;
+use std::fmt::Debug;
 use std::rc::Rc;
 
+type ParseFn<'f, A, S> = dyn Fn(&mut S, &mut ParseLogger) -> Option<A> + 'f;
+
 /// # `Parser` struct
 /// Wraps the parser function.
 #[derive(Clone)]
-pub struct Parser<'f, A, S>(Rc<dyn Fn(&mut S, &mut ParseLogger) -> Option<A> + 'f>);
+pub struct Parser<'f, A, S>(Rc<ParseFn<'f, A, S>>);
 
 impl<'f, A: 'f, S> Parser<'f, A, S> {
     pub fn new<F>(f: F) -> Self
@@ -33,6 +37,45 @@ pub trait Parsable {
         (self.parse(stream, &mut logger), logger)
     }
 
+    /// Parse the whole stream, failing if any input is left.
+    /// Returns the result, or the furthest error on failure.
+    ///
+    /// # Example
+    /// ```
+    /// use parsic::combinators::*;
+    /// use parsic::core::Parsable;
+    /// use parsic::primitives::{char, CharStream};
+    ///
+    /// let parser = char('a').many();
+    ///
+    /// let mut st = CharStream::new("aab");
+    /// let err = parser.parse_complete(&mut st).unwrap_err();
+    ///
+    /// assert_eq!("unexpected 'b', expected one of 'a', end of input (row: 0, col: 2)", err.to_string());
+    /// assert_eq!(Some(2), err.pos.map(|p| p.offset()));
+    /// ```
+    fn parse_complete(&self, stream: &mut Self::Stream) -> Result<Self::Result, ParseError>
+    where
+        Self::Stream: Stream,
+        <Self::Stream as Iterator>::Item: Debug,
+    {
+        let mut logger = ParseLogger::default();
+        let res = self.parse(stream, &mut logger);
+        match (res, stream.peek()) {
+            (Some(x), None) => return Ok(x),
+            (Some(_), Some(next)) => logger.fail(
+                ParseError::new(Some(stream.pos()))
+                    .with_unexpected(&format!("{:?}", next))
+                    .with_expected("end of input"),
+            ),
+            (None, _) => (),
+        }
+        Err(logger
+            .error()
+            .cloned()
+            .unwrap_or_else(|| ParseError::new(Some(stream.pos()))))
+    }
+
     /// Convert into a Parser
     fn into_parser<'f>(self) -> Parser<'f, Self::Result, Self::Stream>
     where
@@ -46,7 +89,7 @@ impl<'f, A: 'f, S> Parsable for Parser<'f, A, S> {
     type Stream = S;
     type Result = A;
     fn parse(&self, stream: &mut Self::Stream, logger: &mut ParseLogger) -> Option<Self::Result> {
-        (*self).0(stream, logger)
+        (self.0)(stream, logger)
     }
 }
 
@@ -68,3 +111,49 @@ where
         (*self)().parse(stream, logger)
     }
 }
+
+#[cfg(test)]
+mod test_parser {
+    use crate::combinators::*;
+    use crate::core::{Parsable, ParseError};
+    use crate::primitives::{char, satisfy, CharStream, ParseAllExt};
+    use std::error::Error;
+
+    fn pair(inp: &str) -> Result<(u32, u32), Box<dyn Error>> {
+        let digit = || satisfy(|ch: &char| ch.is_ascii_digit()).map(|ch| ch as u32 - '0' as u32);
+        let res = digit().left(char(',')).and(digit()).parse_all(inp)?;
+        Ok(res)
+    }
+
+    #[test]
+    fn parse_all_with_question_mark() {
+        assert_eq!((1, 2), pair("1,2").unwrap());
+        assert_eq!(
+            "unexpected 'x', expected ',' (row: 0, col: 1)",
+            pair("1x2").unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn parse_complete_trailing_input() {
+        let parser = char('a');
+
+        let mut st = CharStream::new("ab");
+        let err = parser.parse_complete(&mut st).unwrap_err();
+
+        assert_eq!(
+            "unexpected 'b', expected end of input (row: 0, col: 1)",
+            err.to_string()
+        );
+        assert_eq!("b", st.as_str());
+    }
+
+    #[test]
+    fn parse_complete_without_error() {
+        let parser = empty::<char, CharStream>();
+
+        let err = parser.parse_all("a").unwrap_err();
+
+        assert_eq!(ParseError::new(Some(CharStream::new("").pos())), err);
+    }
+}
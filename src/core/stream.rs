@@ -0,0 +1,104 @@
+use crate::core::Pos;
+
+/// # `Stream` trait
+/// Anything that could be consumed by primitive parsers should
+/// implement `Stream` trait. A stream is an iterator over its items
+/// that can report its current position, be saved and restored
+/// cheaply, and expose what is left of the input.
+pub trait Stream: Iterator + Clone {
+    /// Type of the remaining input, e.g. `&str` for `CharStream`.
+    type Slice;
+
+    /// Return remaining input
+    fn as_slice(&self) -> Self::Slice;
+
+    /// Return current position of parsing
+    fn pos(&self) -> Pos;
+
+    /// Return number of items consumed so far
+    fn index(&self) -> usize;
+
+    /// Return the position right after the last consumed item,
+    /// which is the same as `pos` unless items are not contiguous
+    fn end_pos(&self) -> Pos {
+        self.pos()
+    }
+
+    /// Return the next item without consuming it
+    fn peek(&self) -> Option<Self::Item> {
+        self.clone().next()
+    }
+
+    /// Save current state of the stream
+    fn checkpoint(&self) -> Self {
+        self.clone()
+    }
+
+    /// Restore the stream to a previously saved state
+    fn reset(&mut self, checkpoint: Self) {
+        *self = checkpoint;
+    }
+}
+
+#[cfg(test)]
+mod test_stream {
+    use crate::combinators::*;
+    use crate::core::{Parsable, Pos, Stream};
+    use crate::primitives::{char, literal, satisfy, PrimitiveExt};
+
+    /// A stream over characters stored in a slice
+    #[derive(Clone)]
+    struct SliceStream<'s>(&'s [char], usize);
+
+    impl<'s> Iterator for SliceStream<'s> {
+        type Item = char;
+        fn next(&mut self) -> Option<char> {
+            let ch = *self.0.get(self.1)?;
+            self.1 += 1;
+            Some(ch)
+        }
+    }
+
+    impl<'s> Stream for SliceStream<'s> {
+        type Slice = &'s [char];
+
+        fn as_slice(&self) -> Self::Slice {
+            &self.0[self.1..]
+        }
+        fn pos(&self) -> Pos {
+            Pos::new(0, self.1)
+        }
+        fn index(&self) -> usize {
+            self.1
+        }
+    }
+
+    #[test]
+    fn primitives_on_custom_stream() {
+        let inp = " let  abc;!".chars().collect::<Vec<_>>();
+        let parser = literal("let")
+            .trim()
+            .right(satisfy(|&ch: &char| ch.is_alphabetic()).some())
+            .left(char(';'));
+
+        let mut st = SliceStream(&inp, 0);
+        let (res, logs) = parser.exec(&mut st);
+
+        assert_eq!(Some(vec!['a', 'b', 'c']), res);
+        assert_eq!(&['!'], st.as_slice());
+        assert_eq!(0, logs.len());
+    }
+
+    #[test]
+    fn fail_with_grace() {
+        let inp = "lex".chars().collect::<Vec<_>>();
+        let parser = literal("let");
+
+        let mut st = SliceStream(&inp, 0);
+        let (res, logs) = parser.exec(&mut st);
+
+        assert_eq!(None, res);
+        assert_eq!(0, st.index());
+        assert_eq!(1, logs.len());
+    }
+}
@@ -6,5 +6,5 @@
 pub mod combinators;
 /// Definitions of a parser and its friends.
 pub mod core;
-/// `CharStream` and its primitive parser combinators.
+/// Parse streams and their primitive parser combinators.
 pub mod primitives;
@@ -0,0 +1,362 @@
+use crate::core::{Parsable, ParseError, ParseLogger, Parser, Stream};
+use crate::primitives::item;
+use std::convert::TryInto;
+
+/// Helper function that describes a byte from the parse stream
+fn describe(b: Option<u8>) -> String {
+    b.map_or("end of input".to_string(), |b| format!("0x{:02x}", b))
+}
+
+/// Helper function that builds an error with the next byte as the unexpected item
+fn unexpected<S: Stream<Item = u8>>(stream: &S) -> ParseError {
+    ParseError::new(Some(stream.pos())).with_unexpected(&describe(stream.peek()))
+}
+
+/// # Combinator: `satisfy_byte`
+///
+/// Consume a single byte if given function applied
+/// to the next byte from the parse stream yields `true`.
+///
+/// # Example
+/// ```
+/// use parsic::core::Parsable;
+/// use parsic::primitives::{ByteStream, satisfy_byte};
+///
+/// // Consume an ASCII digit
+/// let parser = satisfy_byte(|b| b.is_ascii_digit());
+///
+/// let mut st = ByteStream::new(b"42");
+/// let (res, logs) = parser.exec(&mut st);
+///
+/// assert_eq!(Some(b'4'), res);
+/// assert_eq!(b"2", st.as_bytes());
+/// assert_eq!(0, logs.len());
+/// ```
+pub fn satisfy_byte<'f, S>(f: impl Fn(&u8) -> bool + 'f) -> Parser<'f, u8, S>
+where
+    S: Stream<Item = u8>,
+{
+    item(f, None, describe)
+}
+
+/// # Combinator: `byte`
+///
+/// Consume the given byte from the parse stream.
+/// `byte(x)` is equivalent to `satisfy_byte(|b| *b == x)`,
+/// except for reporting the expected byte on failure.
+///
+/// # Example
+/// ```
+/// use parsic::core::Parsable;
+/// use parsic::primitives::{byte, ByteStream};
+///
+/// let parser = byte(0x7f);
+///
+/// let mut st = ByteStream::new(&[0x7f, 0x45]);
+/// let (res, logs) = parser.exec(&mut st);
+///
+/// assert_eq!(Some(0x7f), res);
+/// assert_eq!(&[0x45], st.as_bytes());
+/// assert_eq!(0, logs.len());
+/// ```
+pub fn byte<'f, S>(b: u8) -> Parser<'f, u8, S>
+where
+    S: Stream<Item = u8>,
+{
+    item(move |x| *x == b, Some(format!("0x{:02x}", b)), describe)
+}
+
+/// # Combinator: `take`
+///
+/// Consume exactly `n` bytes from the parse stream and
+/// return them as a slice borrowed from the input.
+///
+/// # Example
+/// ```
+/// use parsic::core::Parsable;
+/// use parsic::primitives::{take, ByteStream};
+///
+/// let parser = take(3);
+///
+/// let mut st = ByteStream::new(b"GIF89a");
+/// let (res, logs) = parser.exec(&mut st);
+///
+/// assert_eq!(Some(&b"GIF"[..]), res);
+/// assert_eq!(b"89a", st.as_bytes());
+/// assert_eq!(0, logs.len());
+/// ```
+pub fn take<'f, S>(n: usize) -> Parser<'f, &'f [u8], S>
+where
+    S: Stream<Item = u8, Slice = &'f [u8]>,
+{
+    Parser::new(move |stream: &mut S, logger| {
+        let s = stream.as_slice();
+        if s.len() < n {
+            logger.fail(unexpected(stream).with_expected(&format!("{} bytes", n)));
+            return None;
+        }
+        if n > 0 {
+            stream.nth(n - 1);
+            logger.consume();
+        }
+        Some(&s[..n])
+    })
+}
+
+/// # Combinator: `tag`
+///
+/// Consume given sequence of bytes from the parse stream.
+///
+/// # Example
+/// ```
+/// use parsic::core::Parsable;
+/// use parsic::primitives::{tag, ByteStream};
+///
+/// // Consume PNG signature
+/// let parser = tag(b"\x89PNG");
+///
+/// let mut st = ByteStream::new(b"\x89PNG\r\n");
+/// let (res, logs) = parser.exec(&mut st);
+///
+/// assert_eq!(Some(&b"\x89PNG"[..]), res);
+/// assert_eq!(b"\r\n", st.as_bytes());
+/// assert_eq!(0, logs.len());
+/// ```
+pub fn tag<'f, S>(t: &'f [u8]) -> Parser<'f, &'f [u8], S>
+where
+    S: Stream<Item = u8>,
+{
+    Parser::new(move |stream: &mut S, logger| {
+        let st = stream.checkpoint();
+        if t.iter().all(|&b| stream.next() == Some(b)) {
+            if !t.is_empty() {
+                logger.consume();
+            }
+            Some(t)
+        } else {
+            stream.reset(st);
+            logger.fail(unexpected(stream).with_expected(&format!("{:02x?}", t)));
+            None
+        }
+    })
+}
+
+/// Generate parsers for fixed-width numbers.
+macro_rules! number_parsers {
+    ($($name:ident: $ty:ty, $from:ident, $order:literal;)*) => {$(
+        #[doc = concat!("# Combinator: `", stringify!($name), "`\n\n")]
+        #[doc = concat!("Consume a ", $order, " `", stringify!($ty), "`.")]
+        pub fn $name<'f, S>() -> Parser<'f, $ty, S>
+        where
+            S: Stream<Item = u8, Slice = &'f [u8]> + 'f,
+        {
+            Parser::new(move |stream: &mut S, logger| {
+                take(std::mem::size_of::<$ty>())
+                    .parse(stream, logger)
+                    .map(|b| <$ty>::$from(b.try_into().unwrap()))
+            })
+        }
+    )*};
+}
+
+number_parsers! {
+    be_u16: u16, from_be_bytes, "big-endian";
+    be_u32: u32, from_be_bytes, "big-endian";
+    be_u64: u64, from_be_bytes, "big-endian";
+    be_i16: i16, from_be_bytes, "big-endian";
+    be_i32: i32, from_be_bytes, "big-endian";
+    be_i64: i64, from_be_bytes, "big-endian";
+    be_f32: f32, from_be_bytes, "big-endian";
+    be_f64: f64, from_be_bytes, "big-endian";
+    le_u16: u16, from_le_bytes, "little-endian";
+    le_u32: u32, from_le_bytes, "little-endian";
+    le_u64: u64, from_le_bytes, "little-endian";
+    le_i16: i16, from_le_bytes, "little-endian";
+    le_i32: i32, from_le_bytes, "little-endian";
+    le_i64: i64, from_le_bytes, "little-endian";
+    le_f32: f32, from_le_bytes, "little-endian";
+    le_f64: f64, from_le_bytes, "little-endian";
+}
+
+/// Helper function that reads a LEB128 varint, returning
+/// the accumulated bits, the shift and the last byte read.
+fn leb128<S: Stream<Item = u8>>(
+    stream: &mut S,
+    logger: &mut ParseLogger,
+    signed: bool,
+) -> Option<(u64, u32, u8)> {
+    let st = stream.checkpoint();
+    let (mut res, mut shift) = (0u64, 0u32);
+    loop {
+        let b = match stream.next() {
+            Some(b) => b,
+            None => {
+                logger.fail(unexpected(stream).with_expected("LEB128 varint"));
+                stream.reset(st);
+                return None;
+            }
+        };
+        // The 10th byte may only carry the last bit (or its sign extension)
+        let extra = match signed {
+            true => b & 0x7f != 0 && b & 0x7f != 0x7f,
+            false => b & 0x7e != 0,
+        };
+        if shift >= 64 || (shift == 63 && extra) {
+            stream.reset(st);
+            logger.fail(
+                ParseError::new(Some(stream.pos()))
+                    .with_unexpected("out-of-range LEB128 varint")
+                    .with_expected("at most 64 bits"),
+            );
+            return None;
+        }
+        res |= u64::from(b & 0x7f) << shift;
+        shift += 7;
+        if b & 0x80 == 0 {
+            logger.consume();
+            return Some((res, shift, b));
+        }
+    }
+}
+
+/// # Combinator: `uleb128`
+///
+/// Consume an unsigned LEB128 varint.
+///
+/// # Example
+/// ```
+/// use parsic::core::Parsable;
+/// use parsic::primitives::{uleb128, ByteStream};
+///
+/// let parser = uleb128();
+///
+/// let mut st = ByteStream::new(&[0xe5, 0x8e, 0x26, 0xff]);
+/// let (res, logs) = parser.exec(&mut st);
+///
+/// assert_eq!(Some(624485), res);
+/// assert_eq!(&[0xff], st.as_bytes());
+/// assert_eq!(0, logs.len());
+/// ```
+pub fn uleb128<'f, S>() -> Parser<'f, u64, S>
+where
+    S: Stream<Item = u8>,
+{
+    Parser::new(move |stream: &mut S, logger| leb128(stream, logger, false).map(|(res, _, _)| res))
+}
+
+/// # Combinator: `sleb128`
+///
+/// Consume a signed LEB128 varint.
+///
+/// # Example
+/// ```
+/// use parsic::core::Parsable;
+/// use parsic::primitives::{sleb128, ByteStream};
+///
+/// let parser = sleb128();
+///
+/// let mut st = ByteStream::new(&[0xc0, 0xbb, 0x78]);
+/// let (res, logs) = parser.exec(&mut st);
+///
+/// assert_eq!(Some(-123456), res);
+/// assert_eq!(0, st.as_bytes().len());
+/// assert_eq!(0, logs.len());
+/// ```
+pub fn sleb128<'f, S>() -> Parser<'f, i64, S>
+where
+    S: Stream<Item = u8>,
+{
+    Parser::new(move |stream: &mut S, logger| {
+        leb128(stream, logger, true).map(|(res, shift, last)| {
+            if shift < 64 && last & 0x40 != 0 {
+                (res | (!0u64 << shift)) as i64
+            } else {
+                res as i64
+            }
+        })
+    })
+}
+
+#[cfg(test)]
+mod test_binary {
+    use crate::combinators::*;
+    use crate::core::{Msg, Parsable, Pos};
+    use crate::primitives::*;
+
+    #[test]
+    fn fixed_width_numbers() {
+        let mut inp = vec![0x12, 0x34, 0x78, 0x56, 0x34, 0x12];
+        inp.extend_from_slice(&(-2i64).to_be_bytes());
+        inp.extend_from_slice(&1.5f64.to_le_bytes());
+        let parser = be_u16().and(le_u32()).and(be_i64()).and(le_f64());
+
+        let mut st = ByteStream::new(&inp);
+        let (res, logs) = parser.exec(&mut st);
+
+        assert_eq!(Some((((0x1234, 0x12345678), -2), 1.5)), res);
+        assert_eq!(0, st.as_bytes().len());
+        assert_eq!(0, logs.len());
+    }
+
+    #[test]
+    fn generic_satisfy_on_bytes() {
+        let parser = satisfy(|b: &u8| b.is_ascii_digit()).and(satisfy_byte(|b| *b == b'x'));
+
+        let mut st = ByteStream::new(b"4y");
+        let (res, logs) = parser.exec(&mut st);
+
+        assert_eq!(None, res);
+        assert_eq!(b"4y", st.as_bytes());
+        assert_eq!(
+            "unexpected 0x79 (offset: 1)",
+            logs.error().unwrap().to_string()
+        );
+        assert_eq!(
+            Some(b'4'),
+            satisfy(|b: &u8| b.is_ascii_digit()).exec(&mut st).0
+        );
+    }
+
+    #[test]
+    fn leb128_overflow() {
+        let parser = uleb128();
+
+        let mut st = ByteStream::new(&[0xff; 11]);
+        let (res, logs) = parser.exec(&mut st);
+
+        assert_eq!(None, res);
+        assert_eq!(0, st.index());
+        assert_eq!(1, logs.len());
+        assert_eq!(
+            "unexpected out-of-range LEB128 varint, expected at most 64 bits (offset: 0)",
+            logs.error().unwrap().to_string()
+        );
+    }
+
+    #[test]
+    fn leb128_bounds() {
+        let mut max = vec![0xff; 9];
+        max.push(0x01);
+        let mut min = vec![0x80; 9];
+        min.push(0x7f);
+
+        assert_eq!(Some(u64::MAX), uleb128().exec(&mut ByteStream::new(&max)).0);
+        assert_eq!(Some(i64::MIN), sleb128().exec(&mut ByteStream::new(&min)).0);
+    }
+
+    #[test]
+    fn fail_with_offset() {
+        let parser = tag(b"\x7fELF").right(be_u32());
+
+        let mut st = ByteStream::new(b"\x7fELF\x01\x02");
+        let (res, logs) = parser.exec(&mut st);
+
+        assert_eq!(None, res);
+        assert_eq!(0, st.index());
+        match &logs.stack[0] {
+            Msg::Error(body) => assert_eq!(Some(Pos::from_offset(4)), body.pos),
+            _ => panic!("expecting an error"),
+        }
+        assert!(logs.stack[0].to_string().contains("offset: 4"));
+    }
+}
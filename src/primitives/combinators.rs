@@ -1,11 +1,14 @@
 use crate::combinators::*;
-use crate::core::{Msg, MsgBody, Parsable, Parser};
-use crate::primitives::CharStream;
+use crate::core::{Parsable, ParseError, ParseLogger, Parser, Stream};
+use regex::{Captures, Regex};
+use std::fmt::Debug;
 
 /// # Combinator: `satisfy`
 ///
-/// Consume a single character if given function applied
-/// to the next character from the parse stream yields `true`.
+/// Consume a single item if given function applied
+/// to the next item from the parse stream yields `true`.
+/// Works on any `Stream`, e.g. characters of a `CharStream`,
+/// bytes of a `ByteStream` or tokens of a `TokenStream`.
 ///
 /// # Example
 /// ```
@@ -13,7 +16,7 @@ use crate::primitives::CharStream;
 /// use parsic::primitives::{CharStream, satisfy};
 ///
 /// // Consume a uppercase letter
-/// let parser = satisfy(|&ch| ch.is_uppercase());
+/// let parser = satisfy(|ch: &char| ch.is_uppercase());
 ///
 /// let mut st = CharStream::new("Hello");
 /// let (res, logs) = parser.exec(&mut st);
@@ -22,34 +25,56 @@ use crate::primitives::CharStream;
 /// assert_eq!("ello", st.as_str());
 /// assert_eq!(0, logs.len());
 /// ```
-pub fn satisfy<'f>(f: impl Fn(&char) -> bool + 'f) -> Parser<'f, char, CharStream<'f>> {
-    Parser::new(move |stream: &mut CharStream<'f>, logger| {
-        let st = stream.clone();
+pub fn satisfy<'f, T, S>(f: impl Fn(&T) -> bool + 'f) -> Parser<'f, T, S>
+where
+    T: Debug + 'f,
+    S: Stream<Item = T>,
+{
+    item(f, None, describe)
+}
+
+/// Helper function that consumes a single item satisfying given
+/// function, failing with an optional expected item. The next item
+/// is described with `show` on failure.
+pub(crate) fn item<'f, S>(
+    f: impl Fn(&S::Item) -> bool + 'f,
+    expected: Option<String>,
+    show: fn(Option<S::Item>) -> String,
+) -> Parser<'f, S::Item, S>
+where
+    S: Stream,
+    S::Item: 'f,
+{
+    Parser::new(move |stream: &mut S, logger| {
+        let st = stream.checkpoint();
         match stream.next() {
-            Some(ch) if f(&ch) => Some(ch),
-            Some(ch) => {
-                *stream = st;
-                logger.with(Msg::Error(MsgBody::new(
-                    &format!("'{}' does not satisfy required conditions.", ch)[..],
-                    Some(stream.pos()),
-                )));
-                None
+            Some(x) if f(&x) => {
+                logger.consume();
+                Some(x)
             }
-            None => {
-                logger.with(Msg::Error(MsgBody::new(
-                    "unexpected end of input.",
-                    Some(stream.pos()),
-                )));
+            next => {
+                stream.reset(st);
+                let err = ParseError::new(Some(stream.pos())).with_unexpected(&show(next));
+                logger.fail(match &expected {
+                    Some(x) => err.with_expected(x),
+                    None => err,
+                });
                 None
             }
         }
     })
 }
 
+/// Helper function that describes an item from the parse stream
+pub(crate) fn describe<T: Debug>(item: Option<T>) -> String {
+    item.map_or("end of input".to_string(), |x| format!("{:?}", x))
+}
+
 /// # Combinator: `char`
 ///
 /// Consume the given char from the parse stream.
-/// `char(x)` is equivalent to `satisfy(|x: &char| *x == ch)`
+/// `char(x)` is equivalent to `satisfy(|x: &char| *x == ch)`,
+/// except for reporting the expected character on failure.
 ///
 /// # Example
 /// ```
@@ -66,13 +91,16 @@ pub fn satisfy<'f>(f: impl Fn(&char) -> bool + 'f) -> Parser<'f, char, CharStrea
 /// assert_eq!("ello", st.as_str());
 /// assert_eq!(0, logs.len());
 /// ```
-pub fn char<'f>(ch: char) -> Parser<'f, char, CharStream<'f>> {
-    satisfy(move |x| *x == ch)
+pub fn char<'f, S>(ch: char) -> Parser<'f, char, S>
+where
+    S: Stream<Item = char>,
+{
+    item(move |x| *x == ch, Some(format!("{:?}", ch)), describe)
 }
 
 /// # Combinator: `literal`
 ///
-/// Consume given literal string from the parse stream.test
+/// Consume given literal string from the parse stream.
 ///
 /// # Example
 /// ```
@@ -89,17 +117,24 @@ pub fn char<'f>(ch: char) -> Parser<'f, char, CharStream<'f>> {
 /// assert_eq!("!", st.as_str());
 /// assert_eq!(0, logs.len());
 /// ```
-pub fn literal<'f>(s: &'f str) -> Parser<'f, &'f str, CharStream> {
-    Parser::new(move |stream: &mut CharStream<'f>, logger| {
-        if stream.as_str().starts_with(s) {
-            let ret = &stream.as_str()[0..s.len()];
-            stream.take(s.len()).for_each(|_| {});
-            Some(ret)
+pub fn literal<'f, S>(s: &'f str) -> Parser<'f, &'f str, S>
+where
+    S: Stream<Item = char>,
+{
+    Parser::new(move |stream: &mut S, logger| {
+        let st = stream.checkpoint();
+        if s.chars().all(|ch| stream.next() == Some(ch)) {
+            if !s.is_empty() {
+                logger.consume();
+            }
+            Some(s)
         } else {
-            logger.with(Msg::Error(MsgBody::new(
-                &format!("expecting \"{}\".", s)[..],
-                Some(stream.pos()),
-            )));
+            stream.reset(st);
+            logger.fail(
+                ParseError::new(Some(stream.pos()))
+                    .with_unexpected(&describe(stream.peek()))
+                    .with_expected(&format!("{:?}", s)),
+            );
             None
         }
     })
@@ -108,6 +143,11 @@ pub fn literal<'f>(s: &'f str) -> Parser<'f, &'f str, CharStream> {
 /// # Combinator: `regex`
 ///
 /// Consume a literal string that matches given regular expression.
+/// Works on any `Stream` of characters whose remaining input is a `&str`.
+///
+/// The pattern is compiled once, and anchored to the current position
+/// of the stream, so a leading `^` is not needed. Returns an error if
+/// the pattern is invalid.
 ///
 /// # Example
 /// ```
@@ -115,7 +155,7 @@ pub fn literal<'f>(s: &'f str) -> Parser<'f, &'f str, CharStream> {
 /// use parsic::primitives::{CharStream, regex};
 ///
 /// // Consume a date string
-/// let parser = regex(r"^\d{2}/\d{2}/\d{4}");
+/// let parser = regex(r"\d{2}/\d{2}/\d{4}").unwrap();
 ///
 /// let mut st = CharStream::new("10/30/2020!");
 /// let (res, logs) = parser.exec(&mut st);
@@ -123,27 +163,431 @@ pub fn literal<'f>(s: &'f str) -> Parser<'f, &'f str, CharStream> {
 /// assert_eq!(Some("10/30/2020"), res);
 /// assert_eq!("!", st.as_str());
 /// assert_eq!(0, logs.len());
+/// assert!(regex::<CharStream>(r"\d{2").is_err());
+/// ```
+pub fn regex<'f, S>(re: &str) -> Result<Parser<'f, &'f str, S>, regex::Error>
+where
+    S: Stream<Item = char, Slice = &'f str>,
+{
+    Ok(matching(anchored(re)?, re, find))
+}
+
+/// # Combinator: `regex_from`
+///
+/// Same as `regex`, but takes a prebuilt `Regex`, e.g. one created by
+/// `RegexBuilder` with custom options. Only matches at the current
+/// position are accepted, like `regex`.
+///
+/// The `Regex` is used as is, so its options are kept, and a match
+/// that does not start at the current position is rejected. Without a
+/// leading `\A`, a failing search may scan the rest of the input, so
+/// anchor the pattern with `\A` when the parser is applied repeatedly.
+///
+/// # Example
 /// ```
-pub fn regex<'f>(re: &'f str) -> Parser<'f, &'f str, CharStream> {
-    Parser::new(move |stream: &mut CharStream<'f>, logger| {
-        let regex = regex::Regex::new(re).unwrap();
-        let s = stream.as_str();
-        match regex.find(s) {
-            Some(m) if m.start() == 0 => {
-                stream.take(m.end()).for_each(|_| {});
-                Some(&s[0..m.end()])
+/// use parsic::core::Parsable;
+/// use parsic::primitives::{CharStream, regex_from};
+/// use regex::RegexBuilder;
+///
+/// let re = RegexBuilder::new(r"\Aselect").case_insensitive(true).build().unwrap();
+/// let parser = regex_from(re);
+///
+/// let mut st = CharStream::new("SELECT *");
+/// let (res, logs) = parser.exec(&mut st);
+///
+/// assert_eq!(Some("SELECT"), res);
+/// assert_eq!(" *", st.as_str());
+/// assert_eq!(0, logs.len());
+/// ```
+pub fn regex_from<'f, S>(re: Regex) -> Parser<'f, &'f str, S>
+where
+    S: Stream<Item = char, Slice = &'f str>,
+{
+    let expected = re.as_str().to_string();
+    matching(re, &expected, find)
+}
+
+/// # Combinator: `regex_captures`
+///
+/// Same as `regex`, but returns the capture groups of the match, which
+/// could be accessed by index or by name. Group `0` is the whole match.
+///
+/// # Example
+/// ```
+/// use parsic::core::Parsable;
+/// use parsic::primitives::{CharStream, regex_captures};
+///
+/// let parser = regex_captures(r"(?P<key>\w+)=(\d+)").unwrap();
+///
+/// let mut st = CharStream::new("width=80;");
+/// let (res, logs) = parser.exec(&mut st);
+/// let caps = res.unwrap();
+///
+/// assert_eq!("width=80", &caps[0]);
+/// assert_eq!("width", &caps["key"]);
+/// assert_eq!("80", &caps[2]);
+/// assert_eq!(";", st.as_str());
+/// assert_eq!(0, logs.len());
+/// ```
+pub fn regex_captures<'f, S>(re: &str) -> Result<Parser<'f, Captures<'f>, S>, regex::Error>
+where
+    S: Stream<Item = char, Slice = &'f str>,
+{
+    Ok(matching(anchored(re)?, re, |re, s| {
+        let caps = re.captures(s)?;
+        let m = caps.get(0)?;
+        (m.start() == 0).then(|| (m.end(), caps))
+    }))
+}
+
+/// Helper function that compiles given pattern anchored at the start
+pub(crate) fn anchored(re: &str) -> Result<Regex, regex::Error> {
+    // Compile the pattern on its own first, so that it could not close
+    // the group wrapping it
+    Regex::new(re)?;
+    // A trailing comment of a verbose pattern swallows the closing
+    // parenthesis, so end the comment with a line break then. Outside
+    // of verbose mode a line break would be matched literally.
+    Regex::new(&format!(r"\A(?:{})", re)).or_else(|_| Regex::new(&format!("\\A(?:{}\n)", re)))
+}
+
+/// Helper function that finds the match of given regular expression
+/// at the start of the input, returning its length and the match
+fn find<'s>(re: &Regex, s: &'s str) -> Option<(usize, &'s str)> {
+    re.find(s)
+        .filter(|m| m.start() == 0)
+        .map(|m| (m.end(), m.as_str()))
+}
+
+/// Helper function that consumes the input matched by given regular
+/// expression. `f` returns the length of the match and the result.
+fn matching<'f, A: 'f, S>(
+    re: Regex,
+    pattern: &str,
+    f: impl Fn(&Regex, &'f str) -> Option<(usize, A)> + 'f,
+) -> Parser<'f, A, S>
+where
+    S: Stream<Item = char, Slice = &'f str>,
+{
+    let expected = format!("/{}/", pattern);
+    Parser::new(move |stream: &mut S, logger| {
+        let s = stream.as_slice();
+        match f(&re, s) {
+            Some((len, x)) => {
+                advance(stream, logger, len);
+                Some(x)
             }
-            _ => {
-                logger.with(Msg::Error(MsgBody::new(
-                    &format!("expecting \"{}\".", regex.as_str())[..],
-                    Some(stream.pos()),
-                )));
+            None => {
+                logger.fail(
+                    ParseError::new(Some(stream.pos()))
+                        .with_unexpected(&describe(stream.peek()))
+                        .with_expected(&expected),
+                );
+                None
+            }
+        }
+    })
+}
+
+/// # Combinator: `take_while`
+///
+/// Consume characters as long as given function applied to them yields
+/// `true`, and return the consumed input without copying. Works on any
+/// `Stream` of characters whose remaining input is a `&str`. The
+/// combinator always succeeds.
+///
+/// # Example
+/// ```
+/// use parsic::core::Parsable;
+/// use parsic::primitives::{take_while, CharStream};
+///
+/// let parser = take_while(|ch| ch.is_lowercase());
+///
+/// let mut st = CharStream::new("hello World");
+/// let (res, logs) = parser.exec(&mut st);
+///
+/// assert_eq!(Some("hello"), res);
+/// assert_eq!(" World", st.as_str());
+/// assert_eq!(0, logs.len());
+/// ```
+pub fn take_while<'f, S>(f: impl Fn(&char) -> bool + 'f) -> Parser<'f, &'f str, S>
+where
+    S: Stream<Item = char, Slice = &'f str>,
+{
+    Parser::new(move |stream: &mut S, logger| {
+        let s = stream.as_slice();
+        let len = s.find(|ch| !f(&ch)).unwrap_or(s.len());
+        Some(advance(stream, logger, len))
+    })
+}
+
+/// # Combinator: `take_while1`
+///
+/// Same as `take_while`, but fails if no character is consumed.
+///
+/// # Example
+/// ```
+/// use parsic::core::Parsable;
+/// use parsic::primitives::{take_while1, CharStream};
+///
+/// let parser = take_while1(|ch| ch.is_lowercase());
+///
+/// let mut st = CharStream::new("World");
+/// let (res, logs) = parser.exec(&mut st);
+///
+/// assert_eq!(None, res);
+/// assert_eq!("World", st.as_str());
+/// assert_eq!(1, logs.len());
+/// ```
+pub fn take_while1<'f, S>(f: impl Fn(&char) -> bool + 'f) -> Parser<'f, &'f str, S>
+where
+    S: Stream<Item = char, Slice = &'f str>,
+{
+    take_some(f, None)
+}
+
+/// # Combinator: `take_until`
+///
+/// Consume characters until given literal string is found, and return
+/// the consumed input without copying. The literal itself is not
+/// consumed. Fails if the literal is not found in the remaining input.
+///
+/// # Example
+/// ```
+/// use parsic::combinators::*;
+/// use parsic::core::Parsable;
+/// use parsic::primitives::{literal, take_until, CharStream};
+///
+/// let parser = literal("/*").right(take_until("*/")).left(literal("*/"));
+///
+/// let mut st = CharStream::new("/* a * b */c");
+/// let (res, logs) = parser.exec(&mut st);
+///
+/// assert_eq!(Some(" a * b "), res);
+/// assert_eq!("c", st.as_str());
+/// assert_eq!(0, logs.len());
+/// ```
+pub fn take_until<'f, S>(pat: &'f str) -> Parser<'f, &'f str, S>
+where
+    S: Stream<Item = char, Slice = &'f str>,
+{
+    Parser::new(move |stream: &mut S, logger| {
+        let s = stream.as_slice();
+        match s.find(pat) {
+            Some(len) => Some(advance(stream, logger, len)),
+            None => {
+                let mut end = stream.clone();
+                end.by_ref().for_each(drop);
+                logger.fail(
+                    ParseError::new(Some(end.pos()))
+                        .with_unexpected("end of input")
+                        .with_expected(&format!("{:?}", pat)),
+                );
+                None
+            }
+        }
+    })
+}
+
+/// # Combinator: `one_of`
+///
+/// Consume a single character contained in given string, and return
+/// it as a slice of the input.
+///
+/// # Example
+/// ```
+/// use parsic::core::Parsable;
+/// use parsic::primitives::{one_of, CharStream};
+///
+/// let parser = one_of("+-");
+///
+/// let mut st = CharStream::new("-1");
+/// let (res, logs) = parser.exec(&mut st);
+///
+/// assert_eq!(Some("-"), res);
+/// assert_eq!("1", st.as_str());
+/// assert_eq!(0, logs.len());
+/// ```
+pub fn one_of<'f, S>(chars: &'f str) -> Parser<'f, &'f str, S>
+where
+    S: Stream<Item = char, Slice = &'f str>,
+{
+    let expected = chars.chars().map(|ch| format!("{:?}", ch));
+    take_one(move |ch| chars.contains(*ch), expected.collect())
+}
+
+/// # Combinator: `none_of`
+///
+/// Consume a single character not contained in given string, and
+/// return it as a slice of the input.
+///
+/// # Example
+/// ```
+/// use parsic::core::Parsable;
+/// use parsic::primitives::{none_of, CharStream};
+///
+/// let parser = none_of("\"\\");
+///
+/// let mut st = CharStream::new("\"");
+/// let (res, logs) = parser.exec(&mut st);
+///
+/// assert_eq!(None, res);
+/// assert_eq!("\"", st.as_str());
+/// assert_eq!(1, logs.len());
+/// ```
+pub fn none_of<'f, S>(chars: &'f str) -> Parser<'f, &'f str, S>
+where
+    S: Stream<Item = char, Slice = &'f str>,
+{
+    take_one(move |ch| !chars.contains(*ch), vec![])
+}
+
+/// # Combinator: `alpha`
+///
+/// Consume one or more ASCII letters, and return them as a slice of
+/// the input.
+///
+/// # Example
+/// ```
+/// use parsic::combinators::*;
+/// use parsic::core::Parsable;
+/// use parsic::primitives::{alpha, alnum, CharStream};
+///
+/// let ident = alpha().and(alnum().optional());
+///
+/// let mut st = CharStream::new("x86 = 1");
+/// let (res, logs) = ident.exec(&mut st);
+///
+/// assert_eq!(Some(("x", Some("86"))), res);
+/// assert_eq!(" = 1", st.as_str());
+/// assert_eq!(0, logs.len());
+/// ```
+pub fn alpha<'f, S>() -> Parser<'f, &'f str, S>
+where
+    S: Stream<Item = char, Slice = &'f str>,
+{
+    take_some(char::is_ascii_alphabetic, Some("letter"))
+}
+
+/// # Combinator: `digit`
+///
+/// Consume one or more ASCII digits, and return them as a slice of
+/// the input.
+///
+/// # Example
+/// ```
+/// use parsic::combinators::*;
+/// use parsic::core::Parsable;
+/// use parsic::primitives::{digit, CharStream};
+///
+/// let parser = digit().map_result(|s| s.parse::<u32>());
+///
+/// let mut st = CharStream::new("2048px");
+/// let (res, logs) = parser.exec(&mut st);
+///
+/// assert_eq!(Some(2048), res);
+/// assert_eq!("px", st.as_str());
+/// assert_eq!(0, logs.len());
+/// ```
+pub fn digit<'f, S>() -> Parser<'f, &'f str, S>
+where
+    S: Stream<Item = char, Slice = &'f str>,
+{
+    take_some(char::is_ascii_digit, Some("digit"))
+}
+
+/// # Combinator: `alnum`
+///
+/// Consume one or more ASCII letters or digits, and return them as a
+/// slice of the input.
+pub fn alnum<'f, S>() -> Parser<'f, &'f str, S>
+where
+    S: Stream<Item = char, Slice = &'f str>,
+{
+    take_some(char::is_ascii_alphanumeric, Some("letter or digit"))
+}
+
+/// # Combinator: `hex_digit`
+///
+/// Consume one or more ASCII hexadecimal digits, and return them as a
+/// slice of the input.
+pub fn hex_digit<'f, S>() -> Parser<'f, &'f str, S>
+where
+    S: Stream<Item = char, Slice = &'f str>,
+{
+    take_some(char::is_ascii_hexdigit, Some("hexadecimal digit"))
+}
+
+/// Helper function that consumes one or more characters satisfying
+/// given function, failing with an optional expected item.
+fn take_some<'f, S>(
+    f: impl Fn(&char) -> bool + 'f,
+    expected: Option<&'f str>,
+) -> Parser<'f, &'f str, S>
+where
+    S: Stream<Item = char, Slice = &'f str>,
+{
+    Parser::new(move |stream: &mut S, logger| {
+        let s = stream.as_slice();
+        let len = s.find(|ch| !f(&ch)).unwrap_or(s.len());
+        let res = advance(stream, logger, len);
+        let err = || {
+            let err = ParseError::new(Some(stream.pos())).with_unexpected(&describe(stream.peek()));
+            match expected {
+                Some(x) => err.with_expected(x),
+                None => err,
+            }
+        };
+        match len {
+            0 => {
+                logger.fail(err());
                 None
             }
+            _ => {
+                // As with `some`, where the run stops is remembered as an
+                // error, unless there is a further error already
+                let offset = stream.pos().offset();
+                if !matches!(logger.error().and_then(|e| e.pos), Some(p) if p.offset() > offset) {
+                    logger.merge(err());
+                }
+                Some(res)
+            }
+        }
+    })
+}
+
+/// Helper function that consumes a single character satisfying given
+/// function, failing with given expected items.
+fn take_one<'f, S>(f: impl Fn(&char) -> bool + 'f, expected: Vec<String>) -> Parser<'f, &'f str, S>
+where
+    S: Stream<Item = char, Slice = &'f str>,
+{
+    Parser::new(move |stream: &mut S, logger| match stream.peek() {
+        Some(ch) if f(&ch) => Some(advance(stream, logger, ch.len_utf8())),
+        next => {
+            let err = ParseError::new(Some(stream.pos())).with_unexpected(&describe(next));
+            logger.fail(expected.iter().fold(err, |err, x| err.with_expected(x)));
+            None
         }
     })
 }
 
+/// Helper function that consumes the first `len` bytes of the remaining
+/// input, and returns them
+pub(crate) fn advance<'f, S>(stream: &mut S, logger: &mut ParseLogger, len: usize) -> &'f str
+where
+    S: Stream<Item = char, Slice = &'f str>,
+{
+    let s = stream.as_slice();
+    s[..len].chars().for_each(|_| {
+        stream.next();
+    });
+    if len > 0 {
+        logger.consume();
+    }
+    &s[..len]
+}
+
 /// # Combinator: `space`
 ///
 /// Consume a single whitespace character (` `, `\n`, `\r` or `\t`).
@@ -164,8 +608,15 @@ pub fn regex<'f>(re: &'f str) -> Parser<'f, &'f str, CharStream> {
 /// assert_eq!("Hello", st.as_str());
 /// assert_eq!(0, logs.len());
 /// ```
-pub fn space<'f>() -> Parser<'f, char, CharStream<'f>> {
-    char(' ').or(char('\n')).or(char('\r')).or(char('\t'))
+pub fn space<'f, S>() -> Parser<'f, char, S>
+where
+    S: Stream<Item = char> + 'f,
+{
+    char(' ')
+        .or(char('\n'))
+        .or(char('\r'))
+        .or(char('\t'))
+        .label("whitespace")
 }
 
 /// # Combinator: `trim` (function ver.)
@@ -189,14 +640,15 @@ pub fn space<'f>() -> Parser<'f, char, CharStream<'f>> {
 /// assert_eq!("", st.as_str());
 /// assert_eq!(0, logs.len());
 /// ```
-pub fn trim<'f, A: 'f>(
-    p: impl Parsable<Stream = CharStream<'f>, Result = A> + 'f,
-) -> Parser<'f, A, CharStream<'f>> {
+pub fn trim<'f, A: 'f, S>(p: impl Parsable<Stream = S, Result = A> + 'f) -> Parser<'f, A, S>
+where
+    S: Stream<Item = char> + 'f,
+{
     mid(space().many(), p, space().many())
 }
 
-/// Implement `trim` method for `Parsable<CharStream>`:
-pub trait PrimitiveExt<'f, A: 'f>: Parsable<Stream = CharStream<'f>, Result = A> {
+/// Implement `trim` method for parsers over streams of characters.
+pub trait PrimitiveExt<'f, A: 'f, S>: Parsable<Stream = S, Result = A> {
     /// # Combinator: `trim`
     ///
     /// Consume as many whitespace characters (` `, `\n`, `\r` or `\t`)
@@ -218,15 +670,16 @@ pub trait PrimitiveExt<'f, A: 'f>: Parsable<Stream = CharStream<'f>, Result = A>
     /// assert_eq!("", st.as_str());
     /// assert_eq!(0, logs.len());
     /// ```
-    fn trim(self) -> Parser<'f, A, CharStream<'f>>
+    fn trim(self) -> Parser<'f, A, S>
     where
+        S: Stream<Item = char> + 'f,
         Self: Sized + 'f,
     {
         trim(self)
     }
 }
 
-impl<'f, A: 'f, P: Parsable<Stream = CharStream<'f>, Result = A>> PrimitiveExt<'f, A> for P {}
+impl<'f, A: 'f, S, P: Parsable<Stream = S, Result = A>> PrimitiveExt<'f, A, S> for P {}
 
 #[cfg(test)]
 mod test_char {
@@ -253,7 +706,7 @@ mod test_satisfy {
 
     #[test]
     fn fail_with_grace() {
-        let parser = satisfy(|&ch| ch.is_uppercase());
+        let parser = satisfy(|&ch: &char| ch.is_uppercase());
 
         let mut st = CharStream::new("hello");
         let (res, logs) = parser.exec(&mut st);
@@ -285,11 +738,97 @@ mod test_literal {
 #[cfg(test)]
 mod test_regex {
     use crate::core::Parsable;
-    use crate::primitives::{regex, CharStream};
+    use crate::primitives::{regex, regex_captures, regex_from, CharStream};
+    use regex::{Regex, RegexBuilder};
+
+    #[test]
+    fn anchored_at_current_position() {
+        let parser = regex(r"\d+").unwrap();
+
+        let mut st = CharStream::new("x42");
+        let (res, logs) = parser.exec(&mut st);
+
+        assert_eq!(None, res);
+        assert_eq!("x42", st.as_str());
+        assert_eq!(
+            r"unexpected 'x', expected /\d+/ (row: 0, col: 0)",
+            logs.error().unwrap().to_string()
+        );
+    }
+
+    #[test]
+    fn invalid_patterns() {
+        assert!(regex::<CharStream>("(a").is_err());
+        // Must not close the group used for anchoring
+        assert!(regex::<CharStream>("a)|(b").is_err());
+        assert!(regex_captures::<CharStream>("[a-").is_err());
+    }
+
+    #[test]
+    fn alternation_is_anchored() {
+        let parser = regex("a|b").unwrap();
+
+        let mut st = CharStream::new("xb");
+        assert_eq!(None, parser.exec(&mut st).0);
+    }
+
+    #[test]
+    fn prebuilt_regex_not_anchored() {
+        let parser = regex_from(Regex::new(r"\d").unwrap());
+
+        let mut st = CharStream::new("a1");
+        assert_eq!(None, parser.exec(&mut st).0);
+        let mut st = CharStream::new("1a");
+        assert_eq!(Some("1"), parser.exec(&mut st).0);
+
+        let parser = regex_from(Regex::new(r"\d|x").unwrap());
+        let (res, logs) = parser.exec(&mut CharStream::new("ax"));
+        assert_eq!(None, res);
+        assert_eq!(
+            r"unexpected 'a', expected /\d|x/ (row: 0, col: 0)",
+            logs.error().unwrap().to_string()
+        );
+    }
+
+    #[test]
+    fn prebuilt_regex_keeps_options() {
+        let re = RegexBuilder::new("select").case_insensitive(true).build();
+        let parser = regex_from(re.unwrap());
+
+        let mut st = CharStream::new("SELECT *");
+        assert_eq!(Some("SELECT"), parser.exec(&mut st).0);
+        let mut st = CharStream::new("* select");
+        assert_eq!(None, parser.exec(&mut st).0);
+    }
+
+    #[test]
+    fn verbose_trailing_comment() {
+        let parser = regex("(?x) a b c # comment").unwrap();
+
+        let mut st = CharStream::new("abcd");
+        assert_eq!(Some("abc"), parser.exec(&mut st).0);
+        assert_eq!("d", st.as_str());
+
+        let parser = regex_from(Regex::new("(?x) a b c # comment").unwrap());
+        let mut st = CharStream::new("abcd");
+        assert_eq!(Some("abc"), parser.exec(&mut st).0);
+    }
+
+    #[test]
+    fn optional_groups() {
+        let parser = regex_captures(r"(\d+)(?:\.(\d+))?").unwrap();
+
+        let mut st = CharStream::new("3 ");
+        let caps = parser.exec(&mut st).0.unwrap();
+
+        assert_eq!(Some("3"), caps.get(1).map(|m| m.as_str()));
+        assert_eq!(None, caps.get(2));
+        assert_eq!(" ", st.as_str());
+    }
 
     #[test]
     fn fail_with_grace() {
-        let parser = regex(r"^\d{2}/\d{2}/\d{4}");
+        let parser = regex(r"^\d{2}/\d{2}/\d{4}").unwrap();
 
         let mut st = CharStream::new("Hello");
         let (res, logs) = parser.exec(&mut st);
@@ -299,3 +838,79 @@ mod test_regex {
         assert_eq!(1, logs.len());
     }
 }
+
+#[cfg(test)]
+mod test_take {
+    use crate::combinators::*;
+    use crate::core::Parsable;
+    use crate::primitives::*;
+
+    #[test]
+    fn borrow_from_input() {
+        let inp = String::from("héllo wörld");
+        let parser = take_while(|ch| !ch.is_whitespace());
+
+        let mut st = CharStream::new(&inp);
+        let res = parser.exec(&mut st).0.unwrap();
+
+        assert_eq!("héllo", res);
+        assert_eq!(inp.as_ptr(), res.as_ptr());
+        assert_eq!((6, 5), (st.offset(), st.index()));
+    }
+
+    #[test]
+    fn take_while_empty() {
+        let parser = take_while(|ch| ch.is_ascii_digit());
+
+        let mut st = CharStream::new("abc");
+        let (res, logs) = parser.exec(&mut st);
+
+        assert_eq!(Some(""), res);
+        assert_eq!("abc", st.as_str());
+        assert_eq!(0, logs.len());
+    }
+
+    #[test]
+    fn take_until_not_found() {
+        let parser = take_until("*/");
+
+        let mut st = CharStream::new("ab\nc");
+        let (res, logs) = parser.exec(&mut st);
+
+        assert_eq!(None, res);
+        assert_eq!("ab\nc", st.as_str());
+        let err = logs.error().unwrap();
+        assert_eq!(
+            "unexpected end of input, expected \"*/\" (row: 1, col: 1)",
+            err.to_string()
+        );
+        assert_eq!(Some(4), err.pos.map(|p| p.offset()));
+    }
+
+    #[test]
+    fn char_classes() {
+        let parser = hex_digit().and(one_of("xX")).and(alpha()).and(digit());
+
+        let mut st = CharStream::new("0fxAz42");
+        let (res, _) = parser.exec(&mut st);
+        assert_eq!(Some(((("0f", "x"), "Az"), "42")), res);
+
+        let mut st = CharStream::new("0fyA");
+        let (_, logs) = parser.exec(&mut st);
+        assert_eq!(
+            "unexpected 'y', expected one of hexadecimal digit, 'x', 'X' (row: 0, col: 2)",
+            logs.error().unwrap().to_string()
+        );
+    }
+
+    #[test]
+    fn none_of_multi_byte() {
+        let parser = none_of("ab").many();
+
+        let mut st = CharStream::new("é🦀b");
+        let (res, _) = parser.exec(&mut st);
+
+        assert_eq!(Some(vec!["é", "🦀"]), res);
+        assert_eq!("b", st.as_str());
+    }
+}
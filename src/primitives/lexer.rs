@@ -0,0 +1,169 @@
+use crate::core::{Msg, MsgBody, Span};
+use crate::primitives::{anchored, CharStream};
+use regex::{Regex, RegexSet};
+
+/// # Struct: `Token`
+/// A token produced by `Lexer`, carrying its kind,
+/// the text it was lexed from and its span in the source.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Token<'s, K> {
+    pub kind: K,
+    pub text: &'s str,
+    pub span: Span,
+}
+
+/// # Struct: `Lexer`
+/// A lexer built from an ordered list of regular expression rules.
+///
+/// All rules are compiled into a single `RegexSet`, so each position
+/// of the input is scanned once to find out which rules apply. Among
+/// the applicable rules the longest match wins, and ties are broken
+/// by the order of rules, skip rules coming last.
+///
+/// # Example
+/// ```
+/// use parsic::primitives::Lexer;
+///
+/// #[derive(Clone, Copy, Debug, PartialEq)]
+/// enum Kind { If, Ident, Eq, EqEq }
+///
+/// let lexer = Lexer::new(
+///     &[(r"if", Kind::If), (r"[a-z]+", Kind::Ident), (r"==", Kind::EqEq), (r"=", Kind::Eq)],
+///     &[r"\s+", r"//[^\n]*"],
+/// ).unwrap();
+///
+/// let toks = lexer.tokenize("if iffy == x // done").unwrap();
+/// let kinds = toks.iter().map(|t| t.kind).collect::<Vec<_>>();
+///
+/// assert_eq!(vec![Kind::If, Kind::Ident, Kind::EqEq, Kind::Ident], kinds);
+/// assert_eq!("iffy", toks[1].text);
+/// assert_eq!(3, toks[1].span.start.offset());
+/// ```
+#[derive(Clone, Debug)]
+pub struct Lexer<K> {
+    set: RegexSet,
+    rules: Vec<(Regex, Option<K>)>,
+}
+
+impl<K: Clone> Lexer<K> {
+    /// Create a new Lexer from `(pattern, kind)` rules and skip rules
+    pub fn new(rules: &[(&str, K)], skips: &[&str]) -> Result<Self, regex::Error> {
+        let rules = rules
+            .iter()
+            .map(|(re, kind)| (*re, Some(kind.clone())))
+            .chain(skips.iter().map(|re| (*re, None)))
+            .map(|(re, kind)| Ok((anchored(re)?, kind)))
+            .collect::<Result<Vec<_>, regex::Error>>()?;
+        let set = RegexSet::new(rules.iter().map(|(re, _)| re.as_str()))?;
+        Ok(Self { set, rules })
+    }
+
+    /// Split given input into tokens
+    pub fn tokenize<'s>(&self, inp: &'s str) -> Result<Vec<Token<'s, K>>, Msg> {
+        let mut stream = CharStream::new(inp);
+        let mut res = vec![];
+        while !stream.as_str().is_empty() {
+            let s = stream.as_str();
+            let (len, kind) = self
+                .set
+                .matches(s)
+                .into_iter()
+                .map(|i| {
+                    (
+                        self.rules[i]
+                            .0
+                            .find(s)
+                            .filter(|m| m.start() == 0)
+                            .map_or(0, |m| m.end()),
+                        i,
+                    )
+                })
+                .max_by_key(|&(len, i)| (len, std::cmp::Reverse(i)))
+                .filter(|&(len, _)| len > 0)
+                .map(|(len, i)| (len, &self.rules[i].1))
+                .ok_or_else(|| {
+                    Msg::Error(MsgBody::new(
+                        &format!("unexpected character '{}'.", s.chars().next().unwrap())[..],
+                        Some(stream.pos()),
+                    ))
+                })?;
+
+            let start = stream.pos();
+            s[..len].chars().for_each(|_| {
+                stream.next();
+            });
+            if let Some(kind) = kind {
+                res.push(Token {
+                    kind: kind.clone(),
+                    text: &s[..len],
+                    span: Span::new(start, stream.pos()),
+                });
+            }
+        }
+        Ok(res)
+    }
+}
+
+#[cfg(test)]
+mod test_lexer {
+    use crate::combinators::*;
+    use crate::core::{Msg, Parsable, Pos};
+    use crate::primitives::*;
+
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    enum Kind {
+        Num,
+        Plus,
+    }
+
+    fn lexer() -> Lexer<Kind> {
+        Lexer::new(&[(r"\d+", Kind::Num), (r"\+", Kind::Plus)], &[r"\s+"]).unwrap()
+    }
+
+    #[test]
+    fn parse_lexed_tokens() {
+        let toks = lexer().tokenize("1 + 22 + 333").unwrap();
+        let num = satisfy_token(|t: &Token<Kind>| t.kind == Kind::Num)
+            .map_result(|t| t.text.parse::<u64>());
+        let plus = satisfy_token(|t: &Token<Kind>| t.kind == Kind::Plus);
+        let parser = num
+            .clone()
+            .and(plus.right(num).many())
+            .map(|(x, v)| v.iter().fold(x, |acc, y| acc + y));
+
+        let mut st = TokenStream::with_spans(&toks, |t| t.span);
+        let (res, logs) = parser.exec(&mut st);
+
+        assert_eq!(Some(356), res);
+        assert_eq!(5, st.index());
+        assert_eq!(0, logs.len());
+    }
+
+    #[test]
+    fn rules_are_anchored() {
+        assert!(Lexer::new(&[("a)|(b", 0)], &[]).is_err());
+
+        let lexer = Lexer::new(&[("a|b", 0), ("(?x) c # comment", 1)], &[]).unwrap();
+        let toks = lexer.tokenize("bca").unwrap();
+        assert_eq!(
+            vec![0, 1, 0],
+            toks.iter().map(|t| t.kind).collect::<Vec<_>>()
+        );
+        assert!(lexer.tokenize("xb").is_err());
+    }
+
+    #[test]
+    fn fail_with_pos() {
+        let res = lexer().tokenize("1 + ?");
+
+        match res {
+            Err(Msg::Error(body)) => assert_eq!(Some(4), body.pos.map(|p: Pos| p.offset())),
+            _ => panic!("expecting an error"),
+        }
+    }
+
+    #[test]
+    fn invalid_rule() {
+        assert!(Lexer::new(&[(r"(", Kind::Num)], &[]).is_err());
+    }
+}
@@ -1,4 +1,11 @@
+pub mod binary;
 pub mod combinators;
+pub mod lexer;
+pub mod number;
 pub mod stream;
+pub mod string;
+pub mod token;
 
-pub use crate::primitives::{self, combinators::*, stream::*};
+pub use crate::primitives::{
+    self, binary::*, combinators::*, lexer::*, number::*, stream::*, string::*, token::*,
+};
@@ -0,0 +1,425 @@
+use crate::core::{ParseError, Parser, Stream};
+use crate::primitives::{advance, describe};
+use std::any::type_name;
+use std::borrow::Cow;
+use std::fmt::Display;
+use std::num::ParseIntError;
+use std::str::FromStr;
+
+/// # Trait: `Integer`
+/// Integer types that could be parsed by `integer`.
+pub trait Integer: Copy + Display {
+    const MIN: Self;
+    const MAX: Self;
+    const SIGNED: bool;
+
+    /// Convert a string of digits in given radix with an optional sign
+    fn from_str_radix(s: &str, radix: u32) -> Result<Self, ParseIntError>;
+}
+
+macro_rules! impl_integer {
+    ($($t:ty: $signed:expr),*) => {
+        $(impl Integer for $t {
+            const MIN: Self = <$t>::MIN;
+            const MAX: Self = <$t>::MAX;
+            const SIGNED: bool = $signed;
+
+            fn from_str_radix(s: &str, radix: u32) -> Result<Self, ParseIntError> {
+                <$t>::from_str_radix(s, radix)
+            }
+        })*
+    };
+}
+
+impl_integer!(
+    i8: true, i16: true, i32: true, i64: true, i128: true, isize: true,
+    u8: false, u16: false, u32: false, u64: false, u128: false, usize: false
+);
+
+/// # Trait: `Float`
+/// Floating point types that could be parsed by `float`.
+pub trait Float: Copy + FromStr {
+    /// Check if the value is infinite
+    fn is_infinite(self) -> bool;
+}
+
+impl Float for f32 {
+    fn is_infinite(self) -> bool {
+        f32::is_infinite(self)
+    }
+}
+
+impl Float for f64 {
+    fn is_infinite(self) -> bool {
+        f64::is_infinite(self)
+    }
+}
+
+/// # Struct: `FloatConfig`
+/// Configuration of float literals accepted by `float_with`.
+///
+/// - `exponent`: accept exponents like `1e-5`
+/// - `inf_nan`: accept `inf`, `infinity` and `nan` in any case
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct FloatConfig {
+    pub exponent: bool,
+    pub inf_nan: bool,
+}
+
+impl Default for FloatConfig {
+    fn default() -> Self {
+        Self {
+            exponent: true,
+            inf_nan: false,
+        }
+    }
+}
+
+/// # Combinator: `integer`
+///
+/// Consume an integer literal and convert it into `T`. The literal
+/// consists of an optional sign (`-` only for signed types), an
+/// optional radix prefix `0x`, `0o` or `0b` (in either case), and
+/// digits that may be separated by `_`. A literal out of the range of
+/// `T` is reported as an error at the start of the literal.
+///
+/// # Example
+/// ```
+/// use parsic::core::Parsable;
+/// use parsic::primitives::{integer, CharStream, ParseAllExt};
+///
+/// assert_eq!(Ok(-255), integer::<i32, _>().parse_all("-0xff"));
+/// assert_eq!(Ok(1_000_000), integer::<u64, _>().parse_all("1_000_000"));
+/// assert_eq!(Ok(5), integer::<u8, _>().parse_all("0b101"));
+///
+/// let mut st = CharStream::new("256");
+/// let (res, logs) = integer::<u8, _>().exec(&mut st);
+///
+/// assert_eq!(None, res);
+/// assert_eq!(
+///     "unexpected out-of-range integer 256, expected u8 in range 0..=255 (row: 0, col: 0)",
+///     logs.error().unwrap().to_string()
+/// );
+/// ```
+pub fn integer<'f, T: Integer + 'f, S>() -> Parser<'f, T, S>
+where
+    S: Stream<Item = char, Slice = &'f str>,
+{
+    Parser::new(move |stream: &mut S, logger| {
+        let s = stream.as_slice();
+        let sign = match s.chars().next() {
+            Some('+') => 1,
+            Some('-') if T::SIGNED => 1,
+            _ => 0,
+        };
+        let (radix, prefix) = match s.get(sign..sign + 2) {
+            Some("0x") | Some("0X") => (16, 2),
+            Some("0o") | Some("0O") => (8, 2),
+            Some("0b") | Some("0B") => (2, 2),
+            _ => (10, 0),
+        };
+        let start = sign + prefix;
+        let len = start + digits(&s[start..], radix);
+        if len == start {
+            logger.fail(expect_digit(stream, start, radix));
+            return None;
+        }
+
+        // Copy the literal only to drop the radix prefix or `_`
+        let digits = &s[start..len];
+        let text = match (digits.contains('_'), sign, prefix) {
+            (false, _, 0) => Cow::Borrowed(&s[..len]),
+            (false, 0, _) => Cow::Borrowed(digits),
+            _ => Cow::Owned(format!("{}{}", &s[..sign], digits.replace('_', ""))),
+        };
+        match T::from_str_radix(&text, radix) {
+            Ok(x) => {
+                advance(stream, logger, len);
+                Some(x)
+            }
+            Err(_) => {
+                // The literal is well-formed, so there is nothing to backtrack to
+                logger.consume();
+                logger.fail(
+                    ParseError::new(Some(stream.pos()))
+                        .with_unexpected(&format!("out-of-range integer {}", &s[..len]))
+                        .with_expected(&format!(
+                            "{} in range {}..={}",
+                            type_name::<T>(),
+                            T::MIN,
+                            T::MAX
+                        )),
+                );
+                None
+            }
+        }
+    })
+}
+
+/// # Combinator: `float`
+///
+/// Consume a decimal float literal and convert it into `T`, accepting
+/// an optional sign, digits that may be separated by `_`, an optional
+/// fraction and an optional exponent. Same as `float_with` with the
+/// default `FloatConfig`.
+///
+/// # Example
+/// ```
+/// use parsic::core::Parsable;
+/// use parsic::primitives::{float, CharStream};
+///
+/// let mut st = CharStream::new("-1.5e3px");
+/// let (res, logs) = float::<f64, _>().exec(&mut st);
+///
+/// assert_eq!(Some(-1500.0), res);
+/// assert_eq!("px", st.as_str());
+/// assert_eq!(0, logs.len());
+/// ```
+pub fn float<'f, T: Float + 'f, S>() -> Parser<'f, T, S>
+where
+    S: Stream<Item = char, Slice = &'f str>,
+{
+    float_with(FloatConfig::default())
+}
+
+/// # Combinator: `float_with`
+///
+/// Same as `float`, but accepts float literals as configured. A finite
+/// literal too large for `T` is reported as an error at the start of
+/// the literal.
+///
+/// # Example
+/// ```
+/// use parsic::core::Parsable;
+/// use parsic::primitives::{float_with, CharStream, FloatConfig, ParseAllExt};
+///
+/// let config = FloatConfig { exponent: false, inf_nan: true };
+/// let parser = float_with::<f32, _>(config);
+///
+/// assert_eq!(Ok(f32::NEG_INFINITY), parser.parse_all("-Infinity"));
+/// assert!(parser.parse_all("NaN").unwrap().is_nan());
+/// assert!(parser.parse_all("1e5").is_err());
+/// ```
+pub fn float_with<'f, T: Float + 'f, S>(config: FloatConfig) -> Parser<'f, T, S>
+where
+    S: Stream<Item = char, Slice = &'f str>,
+{
+    Parser::new(move |stream: &mut S, logger| {
+        let s = stream.as_slice();
+        let sign = match s.chars().next() {
+            Some('+') | Some('-') => 1,
+            _ => 0,
+        };
+        let len = match inf_nan(&s[sign..]).filter(|_| config.inf_nan) {
+            Some(len) => sign + len,
+            None => match decimal(&s[sign..], config.exponent) {
+                0 => {
+                    logger.fail(expect_digit(stream, sign, 10));
+                    return None;
+                }
+                len => sign + len,
+            },
+        };
+
+        let text = match s[..len].contains('_') {
+            true => Cow::Owned(s[..len].replace('_', "")),
+            false => Cow::Borrowed(&s[..len]),
+        };
+        match text.parse::<T>() {
+            Ok(x) if !x.is_infinite() || inf_nan(&s[sign..len]).is_some() => {
+                advance(stream, logger, len);
+                Some(x)
+            }
+            _ => {
+                logger.consume();
+                logger.fail(
+                    ParseError::new(Some(stream.pos()))
+                        .with_unexpected(&format!("out-of-range float {}", &s[..len]))
+                        .with_expected(&format!("finite {}", type_name::<T>())),
+                );
+                None
+            }
+        }
+    })
+}
+
+/// Helper function that returns the length of the digits in given radix
+/// at the start of `s`. Digits may be separated by `_` after the first one.
+fn digits(s: &str, radix: u32) -> usize {
+    match s.chars().next() {
+        Some(ch) if ch.is_digit(radix) => s
+            .find(|ch: char| !ch.is_digit(radix) && ch != '_')
+            .unwrap_or(s.len()),
+        _ => 0,
+    }
+}
+
+/// Helper function that returns the length of the unsigned decimal float
+/// literal at the start of `s`, or 0 if there is none
+fn decimal(s: &str, exponent: bool) -> usize {
+    let mut len = digits(s, 10);
+    if s[len..].starts_with('.') {
+        match digits(&s[len + 1..], 10) {
+            0 => (),
+            frac => len += 1 + frac,
+        }
+    }
+    if len > 0 && exponent && s[len..].starts_with(['e', 'E']) {
+        let sign = match s[len + 1..].chars().next() {
+            Some('+') | Some('-') => 1,
+            _ => 0,
+        };
+        match digits(&s[len + 1 + sign..], 10) {
+            0 => (),
+            exp => len += 1 + sign + exp,
+        }
+    }
+    len
+}
+
+/// Helper function that returns the length of `inf`, `infinity` or `nan`
+/// at the start of `s` in any case
+fn inf_nan(s: &str) -> Option<usize> {
+    ["infinity", "inf", "nan"]
+        .iter()
+        .find(|word| matches!(s.get(..word.len()), Some(x) if x.eq_ignore_ascii_case(word)))
+        .map(|word| word.len())
+}
+
+/// Helper function that creates an error for a missing digit after the
+/// ASCII sign and prefix of length `len`
+fn expect_digit<'f, S>(stream: &S, len: usize, radix: u32) -> ParseError
+where
+    S: Stream<Item = char, Slice = &'f str>,
+{
+    let mut st = stream.clone();
+    st.by_ref().take(len).for_each(drop);
+    let expected = match radix {
+        16 => "hexadecimal digit",
+        8 => "octal digit",
+        2 => "binary digit",
+        _ => "digit",
+    };
+    ParseError::new(Some(st.pos()))
+        .with_unexpected(&describe(st.peek()))
+        .with_expected(expected)
+}
+
+#[cfg(test)]
+mod test_number {
+    use crate::combinators::*;
+    use crate::core::Parsable;
+    use crate::primitives::*;
+
+    #[test]
+    fn integer_widths() {
+        assert_eq!(Ok(i8::MIN), integer::<i8, _>().parse_all("-128"));
+        let max = u128::MAX.to_string();
+        assert_eq!(Ok(u128::MAX), integer::<u128, _>().parse_all(&max));
+        assert_eq!(Ok(0o17), integer::<usize, _>().parse_all("+0o17"));
+        assert_eq!(Ok(-0xff), integer::<i32, _>().parse_all("-0XfF"));
+        assert_eq!(Ok(0o17), integer::<u8, _>().parse_all("0O17"));
+        assert_eq!(Ok(0b101), integer::<u8, _>().parse_all("0B101"));
+        assert_eq!(
+            Ok(0xdead_beef),
+            integer::<u32, _>().parse_all("0xdead_beef")
+        );
+        assert!(integer::<i8, _>().parse_all("128").is_err());
+    }
+
+    #[test]
+    fn integer_stops_at_non_digits() {
+        let parser = integer::<i32, _>();
+
+        let mut st = CharStream::new("12_3_a");
+        let (res, logs) = parser.exec(&mut st);
+
+        assert_eq!(Some(123), res);
+        assert_eq!("a", st.as_str());
+        assert_eq!(0, logs.len());
+    }
+
+    #[test]
+    fn integer_missing_digits() {
+        let parser = integer::<u8, _>();
+
+        let (_, logs) = parser.exec(&mut CharStream::new("-1"));
+        assert_eq!(
+            "unexpected '-', expected digit (row: 0, col: 0)",
+            logs.error().unwrap().to_string()
+        );
+
+        let mut st = CharStream::new("0xg");
+        let (res, logs) = parser.exec(&mut st);
+        let err = logs.error().unwrap();
+
+        assert_eq!(None, res);
+        assert_eq!("0xg", st.as_str());
+        assert_eq!(
+            "unexpected 'g', expected hexadecimal digit (row: 0, col: 2)",
+            err.to_string()
+        );
+        assert_eq!(Some(2), err.pos.map(|p| p.offset()));
+    }
+
+    #[test]
+    fn overflow_is_fatal() {
+        let parser = char('[')
+            .right(integer::<i16, _>())
+            .or(char('[').right(pure(0)))
+            .or(pure(1));
+
+        let mut st = CharStream::new("[-99999]");
+        let (res, logs) = parser.exec(&mut st);
+        let err = logs.error().unwrap();
+
+        assert_eq!(None, res);
+        assert_eq!("[-99999]", st.as_str());
+        assert_eq!(Some(1), err.pos.map(|p| p.offset()));
+        assert_eq!(
+            "unexpected out-of-range integer -99999, expected i16 in range -32768..=32767 (row: 0, col: 1)",
+            err.to_string()
+        );
+    }
+
+    #[test]
+    fn float_forms() {
+        let parse = |inp| float::<f64, _>().parse_all(inp).ok();
+
+        assert_eq!(Some(42.0), parse("42"));
+        assert_eq!(Some(0.5), parse(".5"));
+        assert_eq!(Some(-1e5), parse("-1e5"));
+        assert_eq!(Some(2.5e-3), parse("2.5E-3"));
+        assert_eq!(Some(1000.5), parse("1_000.5"));
+        assert_eq!(None, parse("inf"));
+        assert_eq!(None, parse("."));
+    }
+
+    #[test]
+    fn float_leaves_incomplete_parts() {
+        let parser = float::<f64, _>();
+
+        let mut st = CharStream::new("1.e");
+        assert_eq!(Some(1.0), parser.exec(&mut st).0);
+        assert_eq!(".e", st.as_str());
+
+        let mut st = CharStream::new("2e+x");
+        assert_eq!(Some(2.0), parser.exec(&mut st).0);
+        assert_eq!("e+x", st.as_str());
+    }
+
+    #[test]
+    fn float_overflow() {
+        let parser = float::<f32, _>();
+
+        let mut st = CharStream::new("1e39");
+        let (res, logs) = parser.exec(&mut st);
+
+        assert_eq!(None, res);
+        assert_eq!(
+            "unexpected out-of-range float 1e39, expected finite f32 (row: 0, col: 0)",
+            logs.error().unwrap().to_string()
+        );
+        assert_eq!(Ok(1e39), float::<f64, _>().parse_all("1e39"));
+    }
+}
@@ -1,23 +1,70 @@
-use crate::core::Pos;
+use crate::core::{Parsable, ParseError, Pos, Span, Stream};
+
+/// # Struct: `PosConfig`
+/// Configuration of how `CharStream` counts lines and columns.
+///
+/// - `one_based`: count lines and columns from 1 instead of 0
+/// - `tab_width`: a tab advances the column to the next multiple
+///   of `tab_width` (relative to the first column)
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct PosConfig {
+    pub one_based: bool,
+    pub tab_width: usize,
+}
+
+impl Default for PosConfig {
+    fn default() -> Self {
+        Self {
+            one_based: false,
+            tab_width: 1,
+        }
+    }
+}
 
 /// # Struct: `CharStream`
 /// An implementation for parse stream consisting of characters.
+///
+/// Positions carry the byte offset, the character offset, and the
+/// line and column as configured by `PosConfig`. `\n`, `\r\n` and
+/// a lone `\r` are all treated as a single line break.
+///
+/// # Example
+/// ```
+/// use parsic::primitives::{CharStream, PosConfig};
+///
+/// let config = PosConfig { one_based: true, tab_width: 4 };
+/// let mut st = CharStream::with_config("ä\r\n\tx", config);
+/// st.nth(3);
+///
+/// assert_eq!((2, 5), (st.pos().row(), st.pos().col()));
+/// assert_eq!((5, 4), (st.pos().offset(), st.pos().char_offset()));
+/// ```
 #[derive(Clone, Debug)]
 pub struct CharStream<'s> {
     pub(crate) inp: std::str::Chars<'s>,
     pub(crate) pos: Pos,
     pub(crate) len: usize,
     pub(crate) idx: usize,
+    pub(crate) config: PosConfig,
+    pub(crate) cr: bool,
 }
 
 impl<'s> CharStream<'s> {
     /// Create a new CharStream instance
     pub fn new(inp: &'s str) -> Self {
+        Self::with_config(inp, PosConfig::default())
+    }
+
+    /// Create a new CharStream instance counting positions as configured
+    pub fn with_config(inp: &'s str, config: PosConfig) -> Self {
+        let base = config.one_based as usize;
         Self {
             inp: inp.chars(),
-            pos: Pos::new(0, 0),
+            pos: Pos::new(base, base),
             len: inp.len(),
             idx: 0,
+            config,
+            cr: false,
         }
     }
 
@@ -29,15 +76,19 @@ impl<'s> CharStream<'s> {
     pub fn pos(&self) -> Pos {
         self.pos
     }
-    /// Return current index
+    /// Return current index in characters
     pub fn index(&self) -> usize {
         self.idx
     }
-    /// Return length of parse string
+    /// Return current offset in bytes
+    pub fn offset(&self) -> usize {
+        self.pos.offset()
+    }
+    /// Return length of the whole input in bytes
     pub fn len(&self) -> usize {
         self.len
     }
-    /// Check if the stream is empty
+    /// Check if the whole input is empty
     pub fn is_empty(&self) -> bool {
         self.len == 0
     }
@@ -48,12 +99,341 @@ impl<'s> Iterator for CharStream<'s> {
     type Item = char;
     fn next(&mut self) -> Option<Self::Item> {
         let ch = self.inp.next()?;
+        let offset = self.len - self.inp.as_str().len();
+        let base = self.config.one_based as usize;
+        let (row, col) = (self.pos.row(), self.pos.col());
 
-        self.pos = match ch {
-            '\n' => Pos::new(self.pos.row() + 1, 0),
-            _ => self.pos.add(1, 0),
+        let (row, col) = match ch {
+            // `\n` right after `\r` belongs to the same line break
+            '\n' if self.cr => (row, col),
+            '\n' | '\r' => (row + 1, base),
+            '\t' => {
+                let width = self.config.tab_width.max(1);
+                (row, base + ((col - base) / width + 1) * width)
+            }
+            _ => (row, col + 1),
         };
+        self.cr = ch == '\r';
         self.idx += 1;
+        self.pos = Pos::new(row, col)
+            .with_offset(offset)
+            .with_char_offset(self.idx);
         Some(ch)
     }
 }
+
+/// Implement Stream trait for CharStream
+impl<'s> Stream for CharStream<'s> {
+    type Slice = &'s str;
+
+    fn as_slice(&self) -> Self::Slice {
+        self.as_str()
+    }
+    fn pos(&self) -> Pos {
+        self.pos
+    }
+    fn index(&self) -> usize {
+        self.idx
+    }
+}
+
+/// Implement `parse_all` for parsers of `CharStream`.
+pub trait ParseAllExt<'s, A>: Parsable<Stream = CharStream<'s>, Result = A> {
+    /// Parse the whole input, failing if any input is left.
+    /// Returns the result, or the furthest error on failure.
+    ///
+    /// # Example
+    /// ```
+    /// use parsic::combinators::*;
+    /// use parsic::core::{Parsable, ParseError};
+    /// use parsic::primitives::{satisfy, CharStream, ParseAllExt};
+    ///
+    /// fn number(inp: &str) -> Result<u32, ParseError> {
+    ///     let digits = satisfy(|ch: &char| ch.is_ascii_digit()).some();
+    ///     let parser = digits.map(|v| v.into_iter().collect::<String>().parse().unwrap());
+    ///     parser.parse_all(inp)
+    /// }
+    ///
+    /// assert_eq!(Ok(42), number("42"));
+    /// assert!(number("42!").is_err());
+    /// ```
+    fn parse_all(&self, inp: &'s str) -> Result<A, ParseError> {
+        self.parse_complete(&mut CharStream::new(inp))
+    }
+}
+
+impl<'s, A, P: Parsable<Stream = CharStream<'s>, Result = A>> ParseAllExt<'s, A> for P {}
+
+/// # Struct: `ByteStream`
+/// An implementation for parse stream consisting of bytes.
+/// Positions are reported as byte offsets.
+#[derive(Clone, Debug)]
+pub struct ByteStream<'s> {
+    pub(crate) inp: &'s [u8],
+    pub(crate) idx: usize,
+}
+
+impl<'s> ByteStream<'s> {
+    /// Create a new ByteStream instance
+    pub fn new(inp: &'s [u8]) -> Self {
+        Self { inp, idx: 0 }
+    }
+
+    /// Return the remaining bytes of parse stream
+    pub fn as_bytes(&self) -> &'s [u8] {
+        &self.inp[self.idx..]
+    }
+    /// Return current position of parsing
+    pub fn pos(&self) -> Pos {
+        Pos::from_offset(self.idx)
+    }
+    /// Return current index
+    pub fn index(&self) -> usize {
+        self.idx
+    }
+    /// Return length of parse input
+    pub fn len(&self) -> usize {
+        self.inp.len()
+    }
+    /// Check if the stream is empty
+    pub fn is_empty(&self) -> bool {
+        self.inp.is_empty()
+    }
+}
+
+/// Implement Iterator trait for ByteStream
+impl<'s> Iterator for ByteStream<'s> {
+    type Item = u8;
+    fn next(&mut self) -> Option<Self::Item> {
+        let b = *self.inp.get(self.idx)?;
+        self.idx += 1;
+        Some(b)
+    }
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.idx = self.inp.len().min(self.idx.saturating_add(n));
+        self.next()
+    }
+}
+
+/// Implement Stream trait for ByteStream
+impl<'s> Stream for ByteStream<'s> {
+    type Slice = &'s [u8];
+
+    fn as_slice(&self) -> Self::Slice {
+        self.as_bytes()
+    }
+    fn pos(&self) -> Pos {
+        self.pos()
+    }
+    fn index(&self) -> usize {
+        self.idx
+    }
+}
+
+/// # Struct: `TokenStream`
+/// An implementation for parse stream consisting of tokens
+/// produced by a separate lexer. Stream items are `&T`.
+///
+/// When tokens come with their source spans, positions reported by
+/// the stream are those of the tokens in the original source,
+/// otherwise they are token indices.
+pub struct TokenStream<'s, T, E = T> {
+    pub(crate) inp: &'s [E],
+    pub(crate) idx: usize,
+    pub(crate) token: fn(&E) -> &T,
+    pub(crate) span: Option<fn(&E) -> Span>,
+}
+
+impl<'s, T> TokenStream<'s, T> {
+    /// Create a new TokenStream instance from tokens without spans
+    pub fn new(inp: &'s [T]) -> Self {
+        Self {
+            inp,
+            idx: 0,
+            token: |t| t,
+            span: None,
+        }
+    }
+
+    /// Create a new TokenStream instance from tokens that know their spans
+    pub fn with_spans(inp: &'s [T], span: fn(&T) -> Span) -> Self {
+        Self {
+            inp,
+            idx: 0,
+            token: |t| t,
+            span: Some(span),
+        }
+    }
+}
+
+impl<'s, T> TokenStream<'s, T, (T, Span)> {
+    /// Create a new TokenStream instance from tokens paired with their spans
+    pub fn spanned(inp: &'s [(T, Span)]) -> Self {
+        Self {
+            inp,
+            idx: 0,
+            token: |(t, _)| t,
+            span: Some(|(_, s)| *s),
+        }
+    }
+}
+
+impl<'s, T, E> TokenStream<'s, T, E> {
+    /// Return the remaining tokens of parse stream
+    pub fn as_slice(&self) -> &'s [E] {
+        &self.inp[self.idx..]
+    }
+    /// Return current position of parsing, which is the start of
+    /// the next token or the end of the last token
+    pub fn pos(&self) -> Pos {
+        match (self.span, self.inp.get(self.idx), self.inp.last()) {
+            (Some(span), Some(t), _) => span(t).start,
+            (Some(span), None, Some(t)) => span(t).end,
+            _ => Pos::from_offset(self.idx),
+        }
+    }
+    /// Return current index
+    pub fn index(&self) -> usize {
+        self.idx
+    }
+    /// Return number of tokens
+    pub fn len(&self) -> usize {
+        self.inp.len()
+    }
+    /// Check if the stream is empty
+    pub fn is_empty(&self) -> bool {
+        self.inp.is_empty()
+    }
+}
+
+impl<'s, T, E> Clone for TokenStream<'s, T, E> {
+    fn clone(&self) -> Self {
+        Self { ..*self }
+    }
+}
+
+impl<'s, T, E> std::fmt::Debug for TokenStream<'s, T, E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TokenStream")
+            .field("idx", &self.idx)
+            .field("len", &self.inp.len())
+            .finish()
+    }
+}
+
+/// Implement Iterator trait for TokenStream
+impl<'s, T: 's, E> Iterator for TokenStream<'s, T, E> {
+    type Item = &'s T;
+    fn next(&mut self) -> Option<Self::Item> {
+        let t = self.inp.get(self.idx)?;
+        self.idx += 1;
+        Some((self.token)(t))
+    }
+}
+
+/// Implement Stream trait for TokenStream
+impl<'s, T: 's, E> Stream for TokenStream<'s, T, E> {
+    type Slice = &'s [E];
+
+    fn as_slice(&self) -> Self::Slice {
+        self.as_slice()
+    }
+    fn pos(&self) -> Pos {
+        self.pos()
+    }
+    fn index(&self) -> usize {
+        self.idx
+    }
+    fn end_pos(&self) -> Pos {
+        match (
+            self.span,
+            self.idx.checked_sub(1).and_then(|i| self.inp.get(i)),
+        ) {
+            (Some(span), Some(t)) => span(t).end,
+            _ => self.pos(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_stream {
+    use crate::combinators::*;
+    use crate::core::Parsable;
+    use crate::primitives::*;
+
+    fn line_col(st: &CharStream) -> (usize, usize) {
+        (st.pos().row(), st.pos().col())
+    }
+
+    #[test]
+    fn byte_stream_nth_past_end() {
+        let mut st = ByteStream::new(b"abc");
+
+        assert_eq!(Some(b'b'), st.nth(1));
+        assert_eq!(None, st.nth(usize::MAX));
+        assert_eq!(3, st.index());
+    }
+
+    #[test]
+    fn multi_line() {
+        let mut st = CharStream::new("ab\ncd\r\nef\rg");
+
+        st.nth(1);
+        assert_eq!((0, 2), line_col(&st));
+        st.next();
+        assert_eq!((1, 0), line_col(&st));
+        st.nth(1);
+        assert_eq!((1, 2), line_col(&st));
+        st.nth(1);
+        assert_eq!((2, 0), line_col(&st));
+        st.nth(2);
+        assert_eq!((3, 0), line_col(&st));
+        assert_eq!((10, 10), (st.offset(), st.index()));
+    }
+
+    #[test]
+    fn non_ascii() {
+        let mut st = CharStream::with_config(
+            "héllo\n世界!",
+            PosConfig {
+                one_based: true,
+                ..PosConfig::default()
+            },
+        );
+        let parser = satisfy(|ch: &char| ch.is_alphabetic())
+            .many()
+            .and(char('\n'));
+        parser.exec(&mut st);
+        satisfy(|ch: &char| ch.is_alphabetic()).many().exec(&mut st);
+
+        assert_eq!((2, 3), line_col(&st));
+        assert_eq!(13, st.pos().offset());
+        assert_eq!(8, st.pos().char_offset());
+        assert_eq!("!", st.as_str());
+    }
+
+    #[test]
+    fn tab_width() {
+        let config = PosConfig {
+            one_based: false,
+            tab_width: 4,
+        };
+        let mut st = CharStream::with_config("\tab\tc", config);
+
+        st.next();
+        assert_eq!((0, 4), line_col(&st));
+        st.nth(2);
+        assert_eq!((0, 8), line_col(&st));
+    }
+
+    #[test]
+    fn pos_on_failure() {
+        let parser = literal("ab\n").and(char('x'));
+
+        let mut st = CharStream::new("ab\ncd");
+        let (_, logs) = parser.exec(&mut st);
+
+        let pos = logs.error().unwrap().pos.unwrap();
+        assert_eq!((1, 0, 3), (pos.row(), pos.col(), pos.offset()));
+    }
+}
@@ -0,0 +1,600 @@
+use crate::core::{ParseError, Parser, Pos, Stream};
+use crate::primitives::{advance, describe};
+
+/// # Struct: `StringConfig`
+/// Configuration of string literals accepted by `string_literal`.
+///
+/// - `quotes`: characters that open and close a string literal
+/// - `escape`: character that starts an escape sequence, if any
+/// - `escapes`: single-character escapes and the characters they stand for
+/// - `hex`: accept `\x7F`, two hexadecimal digits up to `7F`
+/// - `unicode_braced`: accept `\u{1F600}`, one to six hexadecimal digits
+/// - `unicode_fixed`: accept `\uXXXX`, with surrogate pairs combined
+/// - `raw`: accept raw strings like `r"..."` and `r#"..."#`
+/// - `multiline`: accept line breaks inside of a string literal
+/// - `reject_control`: reject other control characters (U+0000 to U+001F)
+///   unless escaped, including in raw strings where they cannot be escaped
+/// - `line_continuation`: skip an escaped line break (`\n` or `\r\n`) and
+///   the whitespaces after it
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct StringConfig {
+    pub quotes: &'static [char],
+    pub escape: Option<char>,
+    pub escapes: &'static [(char, char)],
+    pub hex: bool,
+    pub unicode_braced: bool,
+    pub unicode_fixed: bool,
+    pub raw: bool,
+    pub multiline: bool,
+    pub reject_control: bool,
+    pub line_continuation: bool,
+}
+
+impl StringConfig {
+    /// String literals of JSON
+    pub fn json() -> Self {
+        Self {
+            quotes: &['"'],
+            escape: Some('\\'),
+            escapes: &[
+                ('"', '"'),
+                ('\\', '\\'),
+                ('/', '/'),
+                ('b', '\u{8}'),
+                ('f', '\u{c}'),
+                ('n', '\n'),
+                ('r', '\r'),
+                ('t', '\t'),
+            ],
+            hex: false,
+            unicode_braced: false,
+            unicode_fixed: true,
+            raw: false,
+            multiline: false,
+            reject_control: true,
+            line_continuation: false,
+        }
+    }
+
+    /// String literals of Rust, including raw strings
+    pub fn rust() -> Self {
+        Self {
+            quotes: &['"'],
+            escape: Some('\\'),
+            escapes: &[
+                ('"', '"'),
+                ('\'', '\''),
+                ('\\', '\\'),
+                ('0', '\0'),
+                ('n', '\n'),
+                ('r', '\r'),
+                ('t', '\t'),
+            ],
+            hex: true,
+            unicode_braced: true,
+            unicode_fixed: false,
+            raw: true,
+            multiline: true,
+            reject_control: false,
+            line_continuation: true,
+        }
+    }
+
+    /// Single-quoted strings of POSIX shells, which have no escapes
+    pub fn shell() -> Self {
+        Self {
+            quotes: &['\''],
+            escape: None,
+            escapes: &[],
+            hex: false,
+            unicode_braced: false,
+            unicode_fixed: false,
+            raw: false,
+            multiline: true,
+            reject_control: false,
+            line_continuation: false,
+        }
+    }
+}
+
+impl Default for StringConfig {
+    fn default() -> Self {
+        Self::json()
+    }
+}
+
+/// # Combinator: `string_literal`
+///
+/// Consume a quoted string literal as configured, and return its
+/// content with escape sequences resolved. Once the opening quote
+/// is consumed, an invalid escape or a missing closing quote fails
+/// the parser at the exact position of the problem.
+///
+/// # Example
+/// ```
+/// use parsic::core::Parsable;
+/// use parsic::primitives::{string_literal, CharStream, StringConfig};
+///
+/// let parser = string_literal(StringConfig::json());
+///
+/// let mut st = CharStream::new(r#""tab\t\u00e9\ud83d\ude00", 1"#);
+/// let (res, logs) = parser.exec(&mut st);
+///
+/// assert_eq!(Some("tab\té😀".to_string()), res);
+/// assert_eq!(", 1", st.as_str());
+/// assert_eq!(0, logs.len());
+///
+/// let mut st = CharStream::new(r#""a\qb""#);
+/// let (res, logs) = parser.exec(&mut st);
+/// let err = logs.error().unwrap();
+///
+/// assert_eq!(None, res);
+/// assert_eq!(Some(2), err.pos.map(|p| p.offset()));
+/// assert_eq!(
+///     "unexpected invalid escape \\q, expected escape sequence (row: 0, col: 2)",
+///     err.to_string()
+/// );
+/// ```
+pub fn string_literal<'f, S>(config: StringConfig) -> Parser<'f, String, S>
+where
+    S: Stream<Item = char, Slice = &'f str>,
+{
+    Parser::new(move |stream: &mut S, logger| {
+        let s = stream.as_slice();
+        let res = match raw_start(s, &config) {
+            Some((hashes, quote)) => scan_raw(s, &config, hashes, quote),
+            None => scan(s, &config),
+        };
+        match res {
+            Ok((content, len)) => {
+                advance(stream, logger, len);
+                Some(content)
+            }
+            Err(Invalid(offset, unexpected, expected)) => {
+                // Past the opening quote, the input can only be a broken literal
+                if offset > 0 {
+                    logger.consume();
+                }
+                let err =
+                    ParseError::new(Some(pos_at(stream, offset))).with_unexpected(&unexpected);
+                logger.fail(expected.iter().fold(err, |err, x| err.with_expected(x)));
+                None
+            }
+        }
+    })
+}
+
+/// Failure of scanning a string literal, with the byte offset where it
+/// happens, the unexpected item and the expected items
+struct Invalid(usize, String, Vec<String>);
+
+/// Helper function that scans a quoted string literal at the start of `s`,
+/// returning its content and its length in bytes
+fn scan(s: &str, config: &StringConfig) -> Result<(String, usize), Invalid> {
+    let quote = match s.chars().next() {
+        Some(ch) if config.quotes.contains(&ch) => ch,
+        next => {
+            let mut expected: Vec<_> = config.quotes.iter().map(|q| format!("{:?}", q)).collect();
+            if config.raw {
+                expected.push("raw string".to_string());
+            }
+            return Err(Invalid(0, describe(next), expected));
+        }
+    };
+
+    let mut content = String::new();
+    let mut i = quote.len_utf8();
+    loop {
+        match s[i..].chars().next() {
+            None => return Err(unterminated(s.len(), quote)),
+            Some(ch) if ch == quote => return Ok((content, i + ch.len_utf8())),
+            Some(ch @ '\n') | Some(ch @ '\r') if !config.multiline => {
+                return Err(Invalid(i, describe(Some(ch)), vec![format!("{:?}", quote)]))
+            }
+            Some(ch) if config.reject_control && ch < ' ' && !matches!(ch, '\n' | '\r') => {
+                return Err(Invalid(
+                    i,
+                    describe(Some(ch)),
+                    vec!["escape sequence".to_string()],
+                ))
+            }
+            Some(ch) if Some(ch) == config.escape => {
+                i = unescape(s, i, ch, config, &mut content)?;
+            }
+            Some(ch) => {
+                content.push(ch);
+                i += ch.len_utf8();
+            }
+        }
+    }
+}
+
+/// Helper function that resolves the escape sequence starting with `esc`
+/// at byte `i` of `s`, returning the byte offset right after it
+fn unescape(
+    s: &str,
+    i: usize,
+    esc: char,
+    config: &StringConfig,
+    content: &mut String,
+) -> Result<usize, Invalid> {
+    let j = i + esc.len_utf8();
+    let invalid = |len: usize, expected: &str| {
+        Invalid(
+            i,
+            format!("invalid escape {}", &s[i..j + len]),
+            vec![expected.to_string()],
+        )
+    };
+
+    let ch = match s[j..].chars().next() {
+        Some(ch) => ch,
+        None => {
+            return Err(Invalid(
+                j,
+                describe::<char>(None),
+                vec!["escape sequence".to_string()],
+            ))
+        }
+    };
+    if let Some(&(_, x)) = config.escapes.iter().find(|(c, _)| *c == ch) {
+        content.push(x);
+        return Ok(j + ch.len_utf8());
+    }
+    match ch {
+        _ if config.line_continuation
+            && (s[j..].starts_with('\n') || s[j..].starts_with("\r\n")) =>
+        {
+            Ok(s.len() - s[j..].trim_start().len())
+        }
+        'x' if config.hex => {
+            let len = hex_digits(&s[j + 1..], 2);
+            if len < 2 {
+                return Err(invalid(1 + len, "2 hexadecimal digits"));
+            }
+            match u8::from_str_radix(&s[j + 1..j + 3], 16) {
+                Ok(x) if x.is_ascii() => {
+                    content.push(x as char);
+                    Ok(j + 3)
+                }
+                _ => Err(Invalid(
+                    i,
+                    format!("out-of-range escape {}", &s[i..j + 3]),
+                    vec!["ASCII character".to_string()],
+                )),
+            }
+        }
+        'u' if config.unicode_braced && s[j + 1..].starts_with('{') => {
+            let body = &s[j + 2..];
+            let len = body
+                .find(|c: char| !c.is_ascii_hexdigit() && c != '_')
+                .unwrap_or(body.len());
+            let digits = body[..len].replace('_', "");
+            if !body[len..].starts_with('}') || digits.is_empty() || digits.len() > 6 {
+                return Err(invalid(2 + len, "1 to 6 hexadecimal digits in braces"));
+            }
+            let end = j + 3 + len;
+            match u32::from_str_radix(&digits, 16)
+                .ok()
+                .and_then(std::char::from_u32)
+            {
+                Some(x) => {
+                    content.push(x);
+                    Ok(end)
+                }
+                None => Err(Invalid(
+                    i,
+                    format!("out-of-range escape {}", &s[i..end]),
+                    vec!["unicode scalar value".to_string()],
+                )),
+            }
+        }
+        'u' if config.unicode_fixed => {
+            let len = hex_digits(&s[j + 1..], 4);
+            if len < 4 {
+                return Err(invalid(1 + len, "4 hexadecimal digits"));
+            }
+            let end = j + 5;
+            let unit = u32::from_str_radix(&s[j + 1..end], 16).unwrap();
+            if let Some(x) = std::char::from_u32(unit) {
+                content.push(x);
+                return Ok(end);
+            }
+            let unpaired = |expected: &str| {
+                Invalid(
+                    i,
+                    format!("unpaired surrogate {}", &s[i..end]),
+                    vec![expected.to_string()],
+                )
+            };
+            if unit >= 0xDC00 {
+                return Err(unpaired("unicode scalar value"));
+            }
+            // A high surrogate must be followed by an escaped low surrogate
+            let low = s[end..]
+                .strip_prefix(esc)
+                .and_then(|x| x.strip_prefix('u'))
+                .filter(|x| hex_digits(x, 4) == 4)
+                .map(|x| u32::from_str_radix(&x[..4], 16).unwrap())
+                .filter(|x| (0xDC00..0xE000).contains(x));
+            match low {
+                Some(low) => {
+                    let x = 0x10000 + ((unit - 0xD800) << 10) + (low - 0xDC00);
+                    content.push(std::char::from_u32(x).unwrap());
+                    Ok(end + esc.len_utf8() + 5)
+                }
+                None => Err(unpaired("low surrogate escape")),
+            }
+        }
+        _ => Err(invalid(ch.len_utf8(), "escape sequence")),
+    }
+}
+
+/// Helper function that returns the number of hashes and the opening quote
+/// of a raw string literal at the start of `s`, if any
+fn raw_start(s: &str, config: &StringConfig) -> Option<(usize, char)> {
+    let rest = s.strip_prefix('r').filter(|_| config.raw)?;
+    let hashes = rest.len() - rest.trim_start_matches('#').len();
+    rest[hashes..]
+        .chars()
+        .next()
+        .filter(|ch| config.quotes.contains(ch))
+        .map(|ch| (hashes, ch))
+}
+
+/// Helper function that scans a raw string literal at the start of `s`
+fn scan_raw(
+    s: &str,
+    config: &StringConfig,
+    hashes: usize,
+    quote: char,
+) -> Result<(String, usize), Invalid> {
+    let start = 1 + hashes + quote.len_utf8();
+    let close = format!("{}{}", quote, "#".repeat(hashes));
+    let len = s[start..].find(&close[..]);
+    let line = s[start..].find(|ch: char| match ch {
+        '\n' | '\r' => !config.multiline,
+        _ => config.reject_control && ch < ' ',
+    });
+    match (len, line) {
+        (Some(len), line) if !matches!(line, Some(x) if x < len) => {
+            Ok((s[start..start + len].to_string(), start + len + close.len()))
+        }
+        (_, Some(x)) => Err(Invalid(
+            start + x,
+            describe(s[start + x..].chars().next()),
+            vec![format!("{:?}", close)],
+        )),
+        _ => Err(Invalid(
+            s.len(),
+            describe::<char>(None),
+            vec![format!("{:?}", close)],
+        )),
+    }
+}
+
+/// Helper function that creates an error for a missing closing quote
+fn unterminated(offset: usize, quote: char) -> Invalid {
+    Invalid(offset, describe::<char>(None), vec![format!("{:?}", quote)])
+}
+
+/// Helper function that returns the number of hexadecimal digits at the
+/// start of `s`, up to `max`
+fn hex_digits(s: &str, max: usize) -> usize {
+    s.chars()
+        .take(max)
+        .take_while(char::is_ascii_hexdigit)
+        .count()
+}
+
+/// Helper function that returns the position `offset` bytes after the
+/// current position of the stream
+fn pos_at<'f, S>(stream: &S, offset: usize) -> Pos
+where
+    S: Stream<Item = char, Slice = &'f str>,
+{
+    let mut st = stream.clone();
+    st.by_ref()
+        .take(stream.as_slice()[..offset].chars().count())
+        .for_each(drop);
+    st.pos()
+}
+
+#[cfg(test)]
+mod test_string {
+    use crate::combinators::*;
+    use crate::core::Parsable;
+    use crate::primitives::*;
+
+    /// Helper function that parses `inp` and returns the result along with
+    /// the error offset and message, if any
+    fn run(config: StringConfig, inp: &str) -> (Option<String>, Option<(usize, String)>) {
+        let (res, logs) = string_literal(config).exec(&mut CharStream::new(inp));
+        let err = logs
+            .error()
+            .map(|e| (e.pos.map_or(0, |p| p.offset()), e.to_string()));
+        (res, err)
+    }
+
+    #[test]
+    fn json_strings() {
+        let json = StringConfig::json();
+
+        assert_eq!(Some("".to_string()), run(json, r#""""#).0);
+        assert_eq!(
+            Some("\"\\/\u{8}\u{c}\n\r\t".to_string()),
+            run(json, r#""\"\\\/\b\f\n\r\t""#).0
+        );
+        assert_eq!(
+            Some("é😀".to_string()),
+            run(json, r#""\u00E9\uD83D\uDE00""#).0
+        );
+        assert_eq!(
+            (
+                None,
+                Some((
+                    3,
+                    "unexpected '\\n', expected '\"' (row: 0, col: 3)".to_string()
+                ))
+            ),
+            run(json, "\"ab\ncd\"")
+        );
+        assert_eq!(
+            (
+                None,
+                Some((
+                    2,
+                    "unexpected '\\t', expected escape sequence (row: 0, col: 2)".to_string()
+                ))
+            ),
+            run(json, "\"a\tb\"")
+        );
+    }
+
+    #[test]
+    fn rust_strings() {
+        let rust = StringConfig::rust();
+
+        assert_eq!(
+            Some("a\x7f\u{1F600}\0'".to_string()),
+            run(rust, r#""a\x7f\u{1F_600}\0\'""#).0
+        );
+        assert_eq!(Some("ab".to_string()), run(rust, "\"a\\\n    b\"").0);
+        assert_eq!(Some("ab".to_string()), run(rust, "\"a\\\r\n    b\"").0);
+        assert_eq!(Some("a\tb".to_string()), run(rust, "\"a\tb\"").0);
+        assert_eq!(Some("x\ny".to_string()), run(rust, "\"x\ny\"").0);
+        assert_eq!(Some(r"\n".to_string()), run(rust, r#"r"\n""#).0);
+        assert_eq!(
+            Some(r#"say "hi""#.to_string()),
+            run(rust, r##"r#"say "hi""#"##).0
+        );
+    }
+
+    #[test]
+    fn shell_strings() {
+        let shell = StringConfig::shell();
+
+        assert_eq!(Some(r"a\nb".to_string()), run(shell, r"'a\nb'").0);
+        assert_eq!(None, run(shell, r#""a""#).0);
+    }
+
+    #[test]
+    fn invalid_escapes() {
+        let cases = vec![
+            (
+                StringConfig::rust(),
+                r#""ab\x8""#,
+                3,
+                "unexpected invalid escape \\x8, expected 2 hexadecimal digits (row: 0, col: 3)",
+            ),
+            (
+                StringConfig::rust(),
+                r#""\xff""#,
+                1,
+                "unexpected out-of-range escape \\xff, expected ASCII character (row: 0, col: 1)",
+            ),
+            (
+                StringConfig::rust(),
+                r#""é\u{110000}""#,
+                3,
+                "unexpected out-of-range escape \\u{110000}, expected unicode scalar value (row: 0, col: 2)",
+            ),
+            (
+                StringConfig::rust(),
+                r#""\u{12""#,
+                1,
+                "unexpected invalid escape \\u{12, expected 1 to 6 hexadecimal digits in braces (row: 0, col: 1)",
+            ),
+            (
+                StringConfig::json(),
+                r#""\uD83Dx""#,
+                1,
+                "unexpected unpaired surrogate \\uD83D, expected low surrogate escape (row: 0, col: 1)",
+            ),
+            (
+                StringConfig::json(),
+                r#""\uDE00""#,
+                1,
+                "unexpected unpaired surrogate \\uDE00, expected unicode scalar value (row: 0, col: 1)",
+            ),
+            (
+                StringConfig::json(),
+                r#""\x41""#,
+                1,
+                "unexpected invalid escape \\x, expected escape sequence (row: 0, col: 1)",
+            ),
+        ];
+
+        for (config, inp, offset, msg) in cases {
+            assert_eq!((None, Some((offset, msg.to_string()))), run(config, inp));
+        }
+    }
+
+    #[test]
+    fn unterminated() {
+        assert_eq!(
+            (
+                None,
+                Some((
+                    4,
+                    "unexpected end of input, expected '\"' (row: 0, col: 4)".to_string()
+                ))
+            ),
+            run(StringConfig::json(), r#""abc"#)
+        );
+        assert_eq!(
+            (
+                None,
+                Some((
+                    9,
+                    "unexpected end of input, expected \"\\\"#\" (row: 0, col: 9)".to_string()
+                ))
+            ),
+            run(StringConfig::rust(), r##"r#"abc"  "##)
+        );
+    }
+
+    #[test]
+    fn control_characters() {
+        let strict = StringConfig {
+            raw: true,
+            ..StringConfig::json()
+        };
+
+        assert_eq!(None, run(strict, "\"a\tb\"").0);
+        assert_eq!(
+            (
+                None,
+                Some((
+                    3,
+                    "unexpected '\\t', expected \"\\\"\" (row: 0, col: 3)".to_string()
+                ))
+            ),
+            run(strict, "r\"a\tb\"")
+        );
+        assert_eq!(
+            Some("a\tb".to_string()),
+            run(StringConfig::rust(), "r\"a\tb\"").0
+        );
+    }
+
+    #[test]
+    fn committed_after_opening_quote() {
+        let parser = string_literal(StringConfig::rust())
+            .or(literal("r").map(|x| x.to_string()))
+            .or(pure("other".to_string()));
+
+        let mut st = CharStream::new("rust");
+        assert_eq!(Some("r".to_string()), parser.exec(&mut st).0);
+        assert_eq!("ust", st.as_str());
+
+        let mut st = CharStream::new(r#""\q""#);
+        let (res, logs) = parser.exec(&mut st);
+        assert_eq!(None, res);
+        assert_eq!(r#""\q""#, st.as_str());
+        assert_eq!(
+            "unexpected invalid escape \\q, expected escape sequence (row: 0, col: 1)",
+            logs.error().unwrap().to_string()
+        );
+    }
+}
@@ -0,0 +1,196 @@
+use crate::core::{ParseError, Parser, Stream};
+use crate::primitives::{describe, item, satisfy};
+use std::fmt::Debug;
+
+/// Helper function that builds an error with the next token as the unexpected item
+fn unexpected<'f, T: Debug + 'f, S>(stream: &S) -> ParseError
+where
+    S: Stream<Item = &'f T>,
+{
+    let item = stream
+        .peek()
+        .map_or("end of input".to_string(), |t| format!("{:?}", t));
+    ParseError::new(Some(stream.pos())).with_unexpected(&item)
+}
+
+/// # Combinator: `satisfy_token`
+///
+/// Consume a single token if given function applied
+/// to the next token from the parse stream yields `true`.
+///
+/// # Example
+/// ```
+/// use parsic::core::Parsable;
+/// use parsic::primitives::{satisfy_token, TokenStream};
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Tok { Num(i64), Plus }
+///
+/// let toks = [Tok::Num(1), Tok::Plus];
+///
+/// // Consume a number token
+/// let parser = satisfy_token(|t| matches!(t, Tok::Num(_)));
+///
+/// let mut st = TokenStream::new(&toks);
+/// let (res, logs) = parser.exec(&mut st);
+///
+/// assert_eq!(Some(&Tok::Num(1)), res);
+/// assert_eq!(&[Tok::Plus], st.as_slice());
+/// assert_eq!(0, logs.len());
+/// ```
+pub fn satisfy_token<'f, T, S>(f: impl Fn(&T) -> bool + 'f) -> Parser<'f, &'f T, S>
+where
+    T: Debug + 'f,
+    S: Stream<Item = &'f T>,
+{
+    satisfy(move |t: &&'f T| f(t))
+}
+
+/// # Combinator: `token`
+///
+/// Consume the given token from the parse stream.
+///
+/// # Example
+/// ```
+/// use parsic::core::Parsable;
+/// use parsic::primitives::{token, TokenStream};
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Tok { Num(i64), Plus }
+///
+/// let toks = [Tok::Plus, Tok::Num(1)];
+///
+/// let parser = token(Tok::Plus);
+///
+/// let mut st = TokenStream::new(&toks);
+/// let (res, logs) = parser.exec(&mut st);
+///
+/// assert_eq!(Some(&Tok::Plus), res);
+/// assert_eq!(&[Tok::Num(1)], st.as_slice());
+/// assert_eq!(0, logs.len());
+/// ```
+pub fn token<'f, T, S>(t: T) -> Parser<'f, &'f T, S>
+where
+    T: Debug + PartialEq + 'f,
+    S: Stream<Item = &'f T>,
+{
+    let expected = format!("{:?}", t);
+    item(move |x: &&T| **x == t, Some(expected), describe)
+}
+
+/// # Combinator: `token_map`
+///
+/// Consume a single token if given function applied to the
+/// next token from the parse stream yields `Some(x)`, and returns `x`.
+///
+/// # Example
+/// ```
+/// use parsic::core::Parsable;
+/// use parsic::primitives::{token_map, TokenStream};
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Tok { Num(i64), Plus }
+///
+/// let toks = [Tok::Num(1), Tok::Plus];
+///
+/// // Consume a number token and extract its value
+/// let parser = token_map(|t| match t {
+///     Tok::Num(x) => Some(*x),
+///     _ => None,
+/// });
+///
+/// let mut st = TokenStream::new(&toks);
+/// let (res, logs) = parser.exec(&mut st);
+///
+/// assert_eq!(Some(1), res);
+/// assert_eq!(&[Tok::Plus], st.as_slice());
+/// assert_eq!(0, logs.len());
+/// ```
+pub fn token_map<'f, T, B: 'f, S>(f: impl Fn(&T) -> Option<B> + 'f) -> Parser<'f, B, S>
+where
+    T: Debug + 'f,
+    S: Stream<Item = &'f T>,
+{
+    Parser::new(move |stream: &mut S, logger| {
+        let st = stream.checkpoint();
+        match stream.next() {
+            Some(t) => f(t).inspect(|_| logger.consume()).or_else(|| {
+                stream.reset(st);
+                logger.fail(unexpected(stream));
+                None
+            }),
+            None => {
+                logger.fail(unexpected(stream));
+                None
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod test_token {
+    use crate::combinators::*;
+    use crate::core::{Msg, Parsable, Parser, Pos, Span, Stream};
+    use crate::primitives::*;
+
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    enum Tok {
+        Num(i64),
+        Plus,
+    }
+
+    fn num<'f, S: Stream<Item = &'f Tok>>() -> Parser<'f, i64, S> {
+        token_map(|t| match t {
+            Tok::Num(x) => Some(*x),
+            _ => None,
+        })
+    }
+
+    #[test]
+    fn sum_of_tokens() {
+        let toks = [Tok::Num(1), Tok::Plus, Tok::Num(2), Tok::Plus, Tok::Num(3)];
+        let parser = num()
+            .and(token(Tok::Plus).right(num()).many())
+            .map(|(x, v)| v.iter().fold(x, |acc, y| acc + y));
+
+        let mut st = TokenStream::new(&toks);
+        let (res, logs) = parser.exec(&mut st);
+
+        assert_eq!(Some(6), res);
+        assert_eq!(5, st.index());
+        assert_eq!(0, logs.len());
+    }
+
+    #[test]
+    fn fail_with_source_pos() {
+        let span = |l, r| Span::new(Pos::new(1, l).with_offset(l), Pos::new(1, r).with_offset(r));
+        let toks = [(Tok::Num(1), span(4, 5)), (Tok::Num(2), span(6, 7))];
+        let parser = num().and(token(Tok::Plus));
+
+        let mut st = TokenStream::spanned(&toks);
+        let (res, logs) = parser.exec(&mut st);
+
+        assert_eq!(None, res);
+        assert_eq!(0, st.index());
+        match &logs.stack[0] {
+            Msg::Error(body) => assert_eq!(Some(span(6, 7).start), body.pos),
+            _ => panic!("expecting an error"),
+        }
+    }
+
+    #[test]
+    fn fail_at_end() {
+        let span = |l, r| Span::new(Pos::from_offset(l), Pos::from_offset(r));
+        let toks = [(Tok::Num(1), span(0, 1))];
+        let parser = num().and(token(Tok::Plus));
+
+        let mut st = TokenStream::spanned(&toks);
+        let (res, logs) = parser.exec(&mut st);
+
+        assert_eq!(None, res);
+        match &logs.stack[0] {
+            Msg::Error(body) => assert_eq!(Some(Pos::from_offset(1)), body.pos),
+            _ => panic!("expecting an error"),
+        }
+    }
+}
@@ -12,110 +12,61 @@
 /// expr    := term {('+'|'-') term}
 /// term    := factor {('*'|'/') factor}
 /// factor  := '(' expr ')' | float
-/// float   := uint ['.' uint]
-/// uint    := digit { digit }
-/// digit   := '0' | '1' | ... | '9'
 /// ```
+///
+/// where `float` is the float literal primitive, which also
+/// accepts exponents like `1e5`.
 use parsic::combinators::*;
 use parsic::core::{Parsable, Parser};
 use parsic::primitives::*;
 
-/// digit := '0' | '1' | ... | '9'
-fn digit<'f>() -> Parser<'f, char, CharStream<'f>> {
-    satisfy(|&ch| ch.is_digit(10))
-}
-
-/// uint := digit { digit }
-fn uint<'f>() -> Parser<'f, String, CharStream<'f>> {
-    digit.some().map(|v| v.iter().collect::<String>())
-}
-
-/// float := uint ['.' uint]
-fn float<'f>() -> Parser<'f, f64, CharStream<'f>> {
-    uint.and(char('.').and(uint).optional())
-        .map_result(|(s, r)| {
-            let mut res = s;
-            if let Some((dot, frac)) = r {
-                res.push(dot);
-                res.push_str(&frac[..])
-            }
-            res.parse::<f64>()
-        })
-}
-
 /// factor := '(' expr ')' | float
 fn factor<'f>() -> Parser<'f, f64, CharStream<'f>> {
-    mid(char('('), expr, char(')')).or(float).trim()
+    mid(char('('), expr, char(')')).or(float()).trim()
 }
 
 /// term := factor {('*'|'/') factor}
 fn term<'f>() -> Parser<'f, f64, CharStream<'f>> {
     factor
-        .and(char('*').or(char('/')).and(factor).many())
+        .chainl1(
+            char('*')
+                .or(char('/'))
+                .map(|op| move |x: f64, y: f64| if op == '*' { x * y } else { x / y }),
+        )
         .trim()
-        .map(|(v0, r)| {
-            r.iter().fold(v0, |acc, (op, v)| match op {
-                '*' => acc * v,
-                _ => acc / v,
-            })
-        })
 }
 
 /// expr := term {('+'|'-') term}
 fn expr<'f>() -> Parser<'f, f64, CharStream<'f>> {
-    term.and(char('+').or(char('-')).and(term).many())
-        .trim()
-        .map(|(v0, r)| {
-            r.iter().fold(v0, |acc, (op, v)| match op {
-                '+' => acc + v,
-                _ => acc - v,
-            })
-        })
+    term.chainl1(
+        char('+')
+            .or(char('-'))
+            .map(|op| move |x: f64, y: f64| if op == '+' { x + y } else { x - y }),
+    )
+    .trim()
 }
 
 /// Another equivlent implementation using closures
 fn expr_<'s>() -> impl Parsable<Stream = CharStream<'s>, Result = f64> {
     fix(|expr| {
-        // digit := '0' | '1' | ... | '9'
-        let digit = satisfy(|&ch| ch.is_digit(10));
-        // uint := digit { digit }
-        let uint = digit.some().map(|v| v.iter().collect::<String>());
-        // float := uint ['.' uint]
-        let float = uint
-            .clone()
-            .and(char('.').and(uint).optional())
-            .map_result(|(s, r)| {
-                let mut res = s;
-                if let Some((dot, frac)) = r {
-                    res.push(dot);
-                    res.push_str(&frac[..])
-                }
-                res.parse::<f64>()
-            });
         // factor := '(' expr ')' | float
-        let factor = mid(char('('), expr.clone(), char(')')).or(float).trim();
+        let factor = mid(char('('), expr.clone(), char(')')).or(float()).trim();
         // term := factor {('*'|'/') factor}
         let term = factor
-            .clone()
-            .and(char('*').or(char('/')).and(factor).many())
-            .trim()
-            .map(|(v0, r)| {
-                r.iter().fold(v0, |acc, (op, v)| match op {
-                    '*' => acc * v,
-                    _ => acc / v,
-                })
-            });
-
-        // expr := term {('+'|'-') expr}
-        term.clone()
-            .and(char('+').or(char('-')).and(term).many())
-            .trim()
-            .map(|(v0, r)| {
-                r.iter().fold(v0, |acc, (op, v2)| match op {
-                    '+' => acc + v2,
-                    _ => acc - v2,
-                })
-            })
+            .chainl1(
+                char('*')
+                    .or(char('/'))
+                    .map(|op| move |x: f64, y: f64| if op == '*' { x * y } else { x / y }),
+            )
+            .trim();
+
+        // expr := term {('+'|'-') term}
+        term.chainl1(
+            char('+')
+                .or(char('-'))
+                .map(|op| move |x: f64, y: f64| if op == '+' { x + y } else { x - y }),
+        )
+        .trim()
     })
 }
 
@@ -139,11 +90,13 @@ fn test_helper(input: &str, expected: Option<f64>, rem_str: &str, log_size: usiz
 }
 
 #[test]
+#[allow(clippy::identity_op)]
 fn int_expr() {
     test_helper("2+4*(6+0)/1", Some((2 + 4 * (6 + 0) / 1) as f64), "", 0);
 }
 
 #[test]
+#[allow(clippy::identity_op)]
 fn int_expr_with_whitespace() {
     test_helper(
         "  2  +  4  *  (  6  +  0  )  /  1  ",
@@ -172,3 +125,8 @@ fn float_expr_with_whitespace() {
         0,
     );
 }
+
+#[test]
+fn float_expr_with_exponent() {
+    test_helper("1e5 / 2.5E-1 - .5", Some(1e5 / 2.5e-1 - 0.5), "", 0);
+}